@@ -4,7 +4,7 @@ import { FC, useState, useEffect, useCallback } from "react";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
 import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { BN } from "@coral-xyz/anchor";
-import { useProgram } from "@/hooks/useProgram";
+import { useProgram, beneficiaryIndexAccounts } from "@/hooks/useProgram";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import {
   Icons,
@@ -227,6 +227,8 @@ export const CreateSwitch: FC = () => {
 
       const tx = await program.methods
         .initializeSwitch(switchName, new BN(actualTimeout), beneficiaries, { sol: {} })
+        .accounts({ owner: publicKey, payer: publicKey })
+        .remainingAccounts(beneficiaryIndexAccounts(beneficiaries.map((b) => b.address)))
         .rpc();
 
       console.log("Switch created:", tx);
@@ -3,7 +3,7 @@
 import { FC, useEffect, useState, useCallback } from "react";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
 import { PublicKey } from "@solana/web3.js";
-import { useProgram } from "@/hooks/useProgram";
+import { useProgram, beneficiaryIndexAccounts } from "@/hooks/useProgram";
 import {
   Icons,
   CardHeader,
@@ -100,10 +100,15 @@ export const MySwitches: FC = () => {
 
     setActionLoading(switchData.switchId);
     try {
-      const tx = await program.methods.cancelSwitch(switchData.switchId).rpc();
+      const tx = await program.methods
+        .cancelSwitch(switchData.switchId)
+        .remainingAccounts(
+          beneficiaryIndexAccounts(switchData.beneficiaries.map((b) => b.address))
+        )
+        .rpc();
 
       console.log("Switch canceled:", tx);
-      addToast("success", "Switch canceled", "Account has been closed");
+      addToast("success", "Switch canceled", "The escrow stays open until withdrawn");
       await loadSwitches();
     } catch (error: any) {
       console.error("Error canceling switch:", error);
@@ -137,7 +142,10 @@ export const MySwitches: FC = () => {
     setActionLoading(switchData.switchId);
     try {
       // Trigger expiry
-      const expiryTx = await program.methods.triggerExpiry(switchData.switchId).rpc();
+      const expiryTx = await program.methods
+        .triggerExpiry(switchData.switchId)
+        .accounts({ switch: switchData.publicKey, profile: null })
+        .rpc();
       console.log("Expiry triggered:", expiryTx);
 
       // Distribute to beneficiaries
@@ -145,7 +153,7 @@ export const MySwitches: FC = () => {
         try {
           const distributeTx = await program.methods
             .distributeSol()
-            .accounts({ beneficiary: beneficiary.address })
+            .accounts({ switch: switchData.publicKey, beneficiary: beneficiary.address })
             .rpc();
           console.log("Distribution tx:", distributeTx);
         } catch (distError: any) {
@@ -21,3 +21,15 @@ export function useProgram() {
     return new Program(idl as any, provider) as Program<Deadmansswitch>;
   }, [connection, wallet]);
 }
+
+/** Writable beneficiary index PDAs, passed to instructions that create or close a switch */
+export function beneficiaryIndexAccounts(beneficiaries: PublicKey[]) {
+  return beneficiaries.map((beneficiary) => ({
+    pubkey: PublicKey.findProgramAddressSync(
+      [Buffer.from("beneficiary_index"), beneficiary.toBuffer()],
+      programId
+    )[0],
+    isSigner: false,
+    isWritable: true,
+  }));
+}
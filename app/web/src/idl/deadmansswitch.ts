@@ -14,19 +14,20 @@ export type Deadmansswitch = {
   },
   "instructions": [
     {
-      "name": "cancelSwitch",
+      "name": "abortCancel",
       "docs": [
-        "Cancel the switch and return funds to owner"
+        "Withdraw a pending cancel request, frozen or not. A frozen request keeps its",
+        "votes, and no new request can be made until the voters thaw it."
       ],
       "discriminator": [
-        126,
-        108,
-        235,
-        9,
-        11,
-        164,
-        232,
-        178
+        37,
+        142,
+        148,
+        134,
+        99,
+        54,
+        96,
+        47
       ],
       "accounts": [
         {
@@ -58,7 +59,6 @@ export type Deadmansswitch = {
         },
         {
           "name": "owner",
-          "writable": true,
           "signer": true,
           "relations": [
             "switch"
@@ -73,23 +73,27 @@ export type Deadmansswitch = {
       ]
     },
     {
-      "name": "distributeAsset",
+      "name": "applyPendingConfigChange",
       "docs": [
-        "Distribute specific asset amount to beneficiary (enhanced version)"
+        "Apply the pending config change once its delay has passed. Anyone can crank it,",
+        "but only while the switch is active and not past its deadline, so an expiry",
+        "during the delay is settled under the old configuration.",
+        "Remaining accounts are the old and new beneficiaries' index PDAs for a beneficiary change."
       ],
       "discriminator": [
-        117,
-        26,
-        134,
-        108,
-        27,
-        159,
-        126,
-        102
+        215,
+        204,
+        178,
+        251,
+        62,
+        61,
+        87,
+        95
       ],
       "accounts": [
         {
           "name": "switch",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -109,96 +113,58 @@ export type Deadmansswitch = {
                 "account": "switch"
               },
               {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
-              }
-            ]
-          }
-        },
-        {
-          "name": "escrow",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  101,
-                  115,
-                  99,
-                  114,
-                  111,
-                  119
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "switch.owner",
-                "account": "switch"
-              },
-              {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
+                "kind": "arg",
+                "path": "switchId"
               }
             ]
           }
         },
         {
-          "name": "escrowTokenAccount",
-          "writable": true
-        },
-        {
-          "name": "beneficiary",
-          "writable": true
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "optional": true
         },
         {
-          "name": "beneficiaryTokenAccount",
-          "writable": true
+          "name": "payer",
+          "docs": [
+            "Pays for new beneficiary index PDAs"
+          ],
+          "writable": true,
+          "signer": true
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
-        },
-        {
-          "name": "assetType",
-          "type": {
-            "defined": {
-              "name": "assetType"
-            }
-          }
+          "name": "switchId",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "distributeSol",
+      "name": "cancelConfigChange",
       "docs": [
-        "Distribute SOL from escrow to beneficiaries"
+        "Drop the pending config change before it is applied"
       ],
       "discriminator": [
-        234,
-        85,
-        98,
-        176,
-        165,
-        8,
-        133,
-        95
+        222,
+        114,
+        136,
+        167,
+        183,
+        86,
+        61,
+        158
       ],
       "accounts": [
         {
           "name": "switch",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -214,75 +180,111 @@ export type Deadmansswitch = {
               },
               {
                 "kind": "account",
-                "path": "switch.owner",
-                "account": "switch"
+                "path": "owner"
               },
               {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
+                "kind": "arg",
+                "path": "switchId"
               }
             ]
           }
         },
         {
-          "name": "escrow",
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "cancelSwitch",
+      "docs": [
+        "Cancel the switch so the owner can take the escrow back with `withdraw_sol`. The",
+        "switch stays open, since the escrow PDA needs it to sign. With a cancel timelock,",
+        "the cancel must have been requested at least the delay ago and not frozen.",
+        "Remaining accounts are the co-owner signers and the beneficiaries' index PDAs."
+      ],
+      "discriminator": [
+        126,
+        108,
+        235,
+        9,
+        11,
+        164,
+        232,
+        178
+      ],
+      "accounts": [
+        {
+          "name": "switch",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  101,
                   115,
+                  119,
+                  105,
+                  116,
                   99,
-                  114,
-                  111,
-                  119
+                  104
                 ]
               },
               {
                 "kind": "account",
-                "path": "switch.owner",
-                "account": "switch"
+                "path": "owner"
               },
               {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
+                "kind": "arg",
+                "path": "switchId"
               }
             ]
           }
         },
         {
-          "name": "beneficiary",
-          "writable": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
     },
     {
-      "name": "distributeSpl",
+      "name": "challengeLiveness",
       "docs": [
-        "Distribute SPL tokens from escrow to beneficiaries"
+        "Beneficiary asks the owner for proof of life. Unless the owner sends a heartbeat",
+        "within LIVENESS_CHALLENGE_SECONDS, the switch becomes expirable early."
       ],
       "discriminator": [
-        93,
-        24,
-        158,
-        238,
-        198,
-        173,
-        215,
-        228
+        54,
+        237,
+        38,
+        255,
+        131,
+        139,
+        231,
+        93
       ],
       "accounts": [
         {
           "name": "switch",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -302,73 +304,46 @@ export type Deadmansswitch = {
                 "account": "switch"
               },
               {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
-              }
-            ]
-          }
-        },
-        {
-          "name": "escrow",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  101,
-                  115,
-                  99,
-                  114,
-                  111,
-                  119
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "switch.owner",
-                "account": "switch"
-              },
-              {
-                "kind": "account",
-                "path": "switch.switch_id",
-                "account": "switch"
+                "kind": "arg",
+                "path": "switchId"
               }
             ]
           }
         },
         {
-          "name": "escrowTokenAccount",
-          "writable": true
-        },
-        {
-          "name": "beneficiary"
-        },
-        {
-          "name": "beneficiaryTokenAccount",
-          "writable": true
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "optional": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "beneficiary",
+          "signer": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
     },
     {
-      "name": "initializeSwitch",
+      "name": "clearDuress",
       "docs": [
-        "Initialize a new Dead Man's Switch with a unique ID"
+        "Guardian vote to lift the duress state; a majority of guardians returns the",
+        "switch to Active"
       ],
       "discriminator": [
-        107,
-        163,
-        228,
-        58,
-        254,
+        186,
         106,
-        204,
-        46
+        171,
+        83,
+        245,
+        33,
+        2,
+        121
       ],
       "accounts": [
         {
@@ -389,7 +364,8 @@ export type Deadmansswitch = {
               },
               {
                 "kind": "account",
-                "path": "owner"
+                "path": "switch.owner",
+                "account": "switch"
               },
               {
                 "kind": "arg",
@@ -399,18 +375,46 @@ export type Deadmansswitch = {
           }
         },
         {
-          "name": "escrow",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
+          "name": "guardian",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "clearExpiryActions",
+      "docs": [
+        "Remove the stored expiry actions and reclaim their rent"
+      ],
+      "discriminator": [
+        217,
+        134,
+        192,
+        80,
+        202,
+        131,
+        189,
+        211
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
                 "value": [
-                  101,
                   115,
+                  119,
+                  105,
+                  116,
                   99,
-                  114,
-                  111,
-                  119
+                  104
                 ]
               },
               {
@@ -422,61 +426,66 @@ export type Deadmansswitch = {
                 "path": "switchId"
               }
             ]
-          }
+          },
+          "relations": [
+            "expiryActions"
+          ]
         },
         {
-          "name": "owner",
+          "name": "expiryActions",
           "writable": true,
-          "signer": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "owner",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
         }
       ],
       "args": [
         {
           "name": "switchId",
           "type": "string"
-        },
-        {
-          "name": "timeoutSeconds",
-          "type": "i64"
-        },
-        {
-          "name": "beneficiaries",
-          "type": {
-            "vec": {
-              "defined": {
-                "name": "beneficiary"
-              }
-            }
-          }
-        },
-        {
-          "name": "tokenType",
-          "type": {
-            "defined": {
-              "name": "tokenType"
-            }
-          }
         }
       ]
     },
     {
-      "name": "initializeSwitchWithAssets",
+      "name": "depositAuthority",
       "docs": [
-        "Initialize a switch with specific asset allocations (enhanced version)"
+        "Hand a program upgrade, mint or freeze authority to the escrow,",
+        "to be passed on to `beneficiary` on expiry"
       ],
       "discriminator": [
-        227,
-        144,
-        50,
-        91,
-        115,
-        209,
-        126,
-        97
+        157,
+        215,
+        216,
+        114,
+        243,
+        55,
+        58,
+        53
       ],
       "accounts": [
         {
@@ -532,14 +541,24 @@ export type Deadmansswitch = {
             ]
           }
         },
+        {
+          "name": "targetAccount",
+          "writable": true
+        },
         {
           "name": "owner",
-          "writable": true,
-          "signer": true
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "bpfLoaderUpgradeable",
+          "address": "BPFLoaderUpgradeab1e11111111111111111111111"
         }
       ],
       "args": [
@@ -548,34 +567,37 @@ export type Deadmansswitch = {
           "type": "string"
         },
         {
-          "name": "timeoutSeconds",
-          "type": "i64"
-        },
-        {
-          "name": "allocations",
+          "name": "kind",
           "type": {
-            "vec": {
-              "defined": {
-                "name": "beneficiaryAllocation"
-              }
+            "defined": {
+              "name": "authorityKind"
             }
           }
+        },
+        {
+          "name": "target",
+          "type": "pubkey"
+        },
+        {
+          "name": "beneficiary",
+          "type": "pubkey"
         }
       ]
     },
     {
-      "name": "sendHeartbeat",
+      "name": "depositStake",
       "docs": [
-        "Send a heartbeat to extend the deadline"
+        "Hand a stake account's staker and withdrawer authorities to the escrow,",
+        "to be passed on to `beneficiary` on expiry"
       ],
       "discriminator": [
-        75,
-        63,
-        64,
-        229,
-        162,
-        200,
-        239,
+        160,
+        167,
+        9,
+        220,
+        74,
+        243,
+        228,
         43
       ],
       "accounts": [
@@ -607,56 +629,23 @@ export type Deadmansswitch = {
           }
         },
         {
-          "name": "owner",
-          "signer": true,
-          "relations": [
-            "switch"
-          ]
-        }
-      ],
-      "args": [
-        {
-          "name": "switchId",
-          "type": "string"
-        }
-      ]
-    },
-    {
-      "name": "triggerExpiry",
-      "docs": [
-        "Trigger expiry and distribute funds to beneficiaries"
-      ],
-      "discriminator": [
-        152,
-        217,
-        89,
-        33,
-        247,
-        243,
-        133,
-        41
-      ],
-      "accounts": [
-        {
-          "name": "switch",
-          "writable": true,
+          "name": "escrow",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  101,
                   115,
-                  119,
-                  105,
-                  116,
                   99,
-                  104
+                  114,
+                  111,
+                  119
                 ]
               },
               {
                 "kind": "account",
-                "path": "switch.owner",
-                "account": "switch"
+                "path": "owner"
               },
               {
                 "kind": "arg",
@@ -664,33 +653,57 @@ export type Deadmansswitch = {
               }
             ]
           }
+        },
+        {
+          "name": "stakeAccount",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
         }
       ],
       "args": [
         {
           "name": "switchId",
           "type": "string"
+        },
+        {
+          "name": "beneficiary",
+          "type": "pubkey"
         }
       ]
     },
     {
-      "name": "withdrawSol",
+      "name": "distributeAsset",
       "docs": [
-        "Withdraw SOL after cancellation"
+        "Distribute specific asset amount to beneficiary (enhanced version)"
       ],
       "discriminator": [
-        145,
-        131,
-        74,
-        136,
-        65,
-        137,
-        42,
-        38
+        117,
+        26,
+        134,
+        108,
+        27,
+        159,
+        126,
+        102
       ],
       "accounts": [
         {
           "name": "switch",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -706,11 +719,13 @@ export type Deadmansswitch = {
               },
               {
                 "kind": "account",
-                "path": "owner"
+                "path": "switch.owner",
+                "account": "switch"
               },
               {
-                "kind": "arg",
-                "path": "switchId"
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
               }
             ]
           }
@@ -733,247 +748,6593 @@ export type Deadmansswitch = {
               },
               {
                 "kind": "account",
-                "path": "owner"
+                "path": "switch.owner",
+                "account": "switch"
               },
               {
-                "kind": "arg",
-                "path": "switchId"
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
               }
             ]
           }
         },
         {
-          "name": "owner",
-          "writable": true,
-          "signer": true,
-          "relations": [
-            "switch"
-          ]
+          "name": "escrowTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "beneficiary",
+          "writable": true
+        },
+        {
+          "name": "beneficiaryTokenAccount",
+          "writable": true
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
       ],
       "args": [
         {
-          "name": "switchId",
-          "type": "string"
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "assetType",
+          "type": {
+            "defined": {
+              "name": "assetType"
+            }
+          }
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "switch",
-      "discriminator": [
-        242,
-        175,
-        214,
-        214,
-        230,
-        53,
-        61,
-        158
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidSwitchId",
-      "msg": "Invalid switch ID (1-32 characters allowed)"
     },
     {
-      "code": 6001,
-      "name": "invalidBeneficiaryCount",
-      "msg": "Invalid number of beneficiaries (1-10 allowed)"
+      "name": "distributeSol",
+      "docs": [
+        "Distribute SOL from escrow to beneficiaries"
+      ],
+      "discriminator": [
+        234,
+        85,
+        98,
+        176,
+        165,
+        8,
+        133,
+        95
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "beneficiary",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6002,
-      "name": "invalidShareDistribution",
-      "msg": "Beneficiary shares must sum to 10000 basis points (100%)"
+      "name": "distributeSpl",
+      "docs": [
+        "Distribute SPL tokens from escrow to beneficiaries"
+      ],
+      "discriminator": [
+        93,
+        24,
+        158,
+        238,
+        198,
+        173,
+        215,
+        228
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrowTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "beneficiary"
+        },
+        {
+          "name": "beneficiaryTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6003,
-      "name": "invalidTimeout",
-      "msg": "Timeout must be positive"
+      "name": "executeExpiryAction",
+      "docs": [
+        "Run one stored expiry action with the escrow as signer.",
+        "Remaining accounts must include every account the action references and its program."
+      ],
+      "discriminator": [
+        10,
+        63,
+        205,
+        122,
+        51,
+        168,
+        255,
+        17
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          },
+          "relations": [
+            "expiryActions"
+          ]
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "expiryActions",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "executorAdjustShares",
+      "docs": [
+        "Executor shifts shares between beneficiaries, each by at most the owner-set bound"
+      ],
+      "discriminator": [
+        202,
+        42,
+        17,
+        102,
+        89,
+        158,
+        167,
+        14
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "executor",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shareAdjustments",
+          "type": {
+            "vec": "i16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "executorFinalize",
+      "docs": [
+        "Executor closes the window early, opening distribution"
+      ],
+      "discriminator": [
+        166,
+        235,
+        152,
+        147,
+        79,
+        107,
+        20,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "executor",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executorPayExpense",
+      "docs": [
+        "Executor pays an owner-approved expense from the escrow, up to its cap"
+      ],
+      "discriminator": [
+        182,
+        167,
+        102,
+        212,
+        173,
+        194,
+        197,
+        223
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payee",
+          "writable": true
+        },
+        {
+          "name": "executor",
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "executorSetDistributionOrder",
+      "docs": [
+        "Executor fixes the order in which beneficiaries receive distributions, as beneficiary indices"
+      ],
+      "discriminator": [
+        143,
+        218,
+        25,
+        109,
+        196,
+        127,
+        97,
+        162
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "executor",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "order",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "freezeCancel",
+      "docs": [
+        "Guardian or beneficiary vote to freeze a pending cancel; the request is frozen",
+        "once `freeze_quorum` distinct voters agree"
+      ],
+      "discriminator": [
+        89,
+        85,
+        249,
+        33,
+        120,
+        231,
+        112,
+        84
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voter",
+          "docs": [
+            "Guardian or beneficiary"
+          ],
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "getStatus",
+      "docs": [
+        "Report the switch's effective status through return data, for CPI callers and simulation"
+      ],
+      "discriminator": [
+        199,
+        6,
+        205,
+        77,
+        193,
+        78,
+        197,
+        110
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrowTokenAccount",
+          "docs": [
+            "Escrow token account, only read for SPL switches"
+          ],
+          "optional": true
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "optional": true
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "switchStatusView"
+        }
+      }
+    },
+    {
+      "name": "initializeCanary",
+      "docs": [
+        "Initialize a fund-free warrant canary: a switch without beneficiaries that publishes",
+        "a statement hash and URI and is renewed by heartbeat. Once expired it is flagged",
+        "as lapsed for good."
+      ],
+      "discriminator": [
+        68,
+        212,
+        140,
+        21,
+        174,
+        134,
+        231,
+        187
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "docs": [
+            "Owner may be a PDA signing through CPI, so it does not pay rent"
+          ],
+          "signer": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "timeoutSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "statementHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "initializeIndexedSwitch",
+      "docs": [
+        "Initialize a switch seeded by the registry's next index instead of a free-form ID.",
+        "Remaining accounts are the beneficiaries' index PDAs."
+      ],
+      "discriminator": [
+        106,
+        219,
+        12,
+        61,
+        250,
+        237,
+        70,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "registry"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "registry"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "registry"
+          ]
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "label",
+          "type": "string"
+        },
+        {
+          "name": "timeoutSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "beneficiaries",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "beneficiary"
+              }
+            }
+          }
+        },
+        {
+          "name": "tokenType",
+          "type": {
+            "defined": {
+              "name": "tokenType"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeProfile",
+      "docs": [
+        "Create the owner's profile, whose single heartbeat keeps every linked switch alive"
+      ],
+      "discriminator": [
+        32,
+        145,
+        77,
+        213,
+        58,
+        39,
+        251,
+        234
+      ],
+      "accounts": [
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "timeoutSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "initializeRegistry",
+      "docs": [
+        "Create the owner's switch registry, which hands out counter-based switch IDs"
+      ],
+      "discriminator": [
+        189,
+        181,
+        20,
+        17,
+        174,
+        57,
+        249,
+        59
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeSwitch",
+      "docs": [
+        "Initialize a new Dead Man's Switch with a unique ID",
+        "Remaining accounts are the beneficiaries' index PDAs."
+      ],
+      "discriminator": [
+        107,
+        163,
+        228,
+        58,
+        254,
+        106,
+        204,
+        46
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "docs": [
+            "Owner may be a PDA signing through CPI, so it does not pay rent"
+          ],
+          "signer": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "timeoutSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "beneficiaries",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "beneficiary"
+              }
+            }
+          }
+        },
+        {
+          "name": "tokenType",
+          "type": {
+            "defined": {
+              "name": "tokenType"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeSwitchWithAssets",
+      "docs": [
+        "Initialize a switch with specific asset allocations (enhanced version)",
+        "Remaining accounts are the beneficiaries' index PDAs."
+      ],
+      "discriminator": [
+        227,
+        144,
+        50,
+        91,
+        115,
+        209,
+        126,
+        97
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "docs": [
+            "Owner may be a PDA signing through CPI, so it does not pay rent"
+          ],
+          "signer": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "timeoutSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "allocations",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "beneficiaryAllocation"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "linkProfile",
+      "docs": [
+        "Link a switch to the owner's profile so the profile deadline applies to it"
+      ],
+      "discriminator": [
+        82,
+        126,
+        109,
+        95,
+        254,
+        120,
+        187,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch",
+            "profile"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "proposeConfigChange",
+      "docs": [
+        "Propose a config change. The change is staged until the config delay has passed,",
+        "or applied at once when no delay is set.",
+        "Remaining accounts are co-owner signers when an owner quorum is set and, for an",
+        "immediate beneficiary change, the old and new beneficiaries' index PDAs."
+      ],
+      "discriminator": [
+        165,
+        15,
+        231,
+        227,
+        223,
+        229,
+        247,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "docs": [
+            "Pays for new beneficiary index PDAs when the change applies at once"
+          ],
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "change",
+          "type": {
+            "defined": {
+              "name": "configChange"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "publishCanary",
+      "docs": [
+        "Publish a new canary statement, which also renews the canary"
+      ],
+      "discriminator": [
+        189,
+        151,
+        17,
+        252,
+        41,
+        223,
+        236,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "statementHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "reclaimAuthority",
+      "docs": [
+        "Return a held authority to the owner. With a cancel timelock, needs a cancel",
+        "request that has waited out the delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        156,
+        147,
+        67,
+        113,
+        251,
+        229,
+        162,
+        166
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "targetAccount",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "bpfLoaderUpgradeable",
+          "address": "BPFLoaderUpgradeab1e11111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": {
+              "name": "authorityKind"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "reclaimStake",
+      "docs": [
+        "Return a registered stake account's authorities to the owner. With a cancel",
+        "timelock, needs a cancel request that has waited out the delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        237,
+        113,
+        219,
+        76,
+        6,
+        246,
+        223,
+        84
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "stakeAccount",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "releaseAuthority",
+      "docs": [
+        "Pass a held authority to its beneficiary after expiry"
+      ],
+      "discriminator": [
+        174,
+        59,
+        62,
+        231,
+        244,
+        121,
+        204,
+        241
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "targetAccount",
+          "writable": true
+        },
+        {
+          "name": "beneficiary"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "bpfLoaderUpgradeable",
+          "address": "BPFLoaderUpgradeab1e11111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "kind",
+          "type": {
+            "defined": {
+              "name": "authorityKind"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "releaseStake",
+      "docs": [
+        "Pass a registered stake account's authorities to its beneficiary after expiry"
+      ],
+      "discriminator": [
+        51,
+        5,
+        28,
+        250,
+        185,
+        168,
+        18,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "stakeAccount",
+          "writable": true
+        },
+        {
+          "name": "beneficiary"
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "requestCancel",
+      "docs": [
+        "Start the cancel timelock. `cancel_switch` becomes possible once the delay has passed",
+        "unless guardians and beneficiaries freeze the request first.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        244,
+        78,
+        42,
+        227,
+        165,
+        174,
+        94,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "reseedHeartbeatChain",
+      "docs": [
+        "Replace an exhausted or exposed hash chain. Revealing the next preimage counts as",
+        "a heartbeat; a `new_tip` of None turns the chain off."
+      ],
+      "discriminator": [
+        139,
+        182,
+        33,
+        80,
+        160,
+        237,
+        149,
+        21
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "preimage",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "newTip",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "reviveSwitch",
+      "docs": [
+        "Revive an expired switch during its challenge period. Hash-chained switches pass the",
+        "next preimage, as for `send_chained_heartbeat`."
+      ],
+      "discriminator": [
+        204,
+        83,
+        251,
+        180,
+        231,
+        215,
+        135,
+        28
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "preimage",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sendChainedHeartbeat",
+      "docs": [
+        "Send a heartbeat on a hash-chained switch by revealing the preimage of the",
+        "last committed value"
+      ],
+      "discriminator": [
+        6,
+        11,
+        89,
+        95,
+        0,
+        187,
+        210,
+        190
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "preimage",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "sendCoOwnerHeartbeat",
+      "docs": [
+        "Send a heartbeat as one of the co-owners of a joint switch"
+      ],
+      "discriminator": [
+        209,
+        16,
+        224,
+        102,
+        61,
+        50,
+        236,
+        113
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "optional": true
+        },
+        {
+          "name": "coOwner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "sendCodedHeartbeat",
+      "docs": [
+        "Send a heartbeat that carries a code. Any code counts as a plain heartbeat, but the",
+        "duress code also moves the switch into the Duress state, freezing cancel and",
+        "withdrawals, and emits `DuressSignaled`. Both look the same to the submitter.",
+        "Hash-chained switches pass the next preimage, as for `send_chained_heartbeat`."
+      ],
+      "discriminator": [
+        229,
+        203,
+        15,
+        83,
+        249,
+        25,
+        198,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "code",
+          "type": "string"
+        },
+        {
+          "name": "preimage",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sendEthHeartbeat",
+      "docs": [
+        "Heartbeat from an Ethereum key, submitted by any fee payer. The preceding instruction",
+        "must be the secp256k1 precompile verifying the registered address's EIP-191",
+        "signature over `eth_heartbeat_message(switch, eth_nonce)`."
+      ],
+      "discriminator": [
+        167,
+        242,
+        51,
+        133,
+        58,
+        19,
+        66,
+        151
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "sendHeartbeat",
+      "docs": [
+        "Send a heartbeat to extend the deadline"
+      ],
+      "discriminator": [
+        75,
+        63,
+        64,
+        229,
+        162,
+        200,
+        239,
+        43
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "sendHeartbeatBatch",
+      "docs": [
+        "Send a heartbeat to every owner switch passed in `remaining_accounts` as",
+        "(switch, heartbeat history) pairs.",
+        "Switches that cannot take a heartbeat are skipped; each result is emitted as an event."
+      ],
+      "discriminator": [
+        1,
+        224,
+        186,
+        165,
+        41,
+        118,
+        30,
+        160
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sendPasskeyHeartbeat",
+      "docs": [
+        "Heartbeat from a WebAuthn passkey assertion, submitted by anyone. The preceding",
+        "instruction must be the secp256r1 precompile verifying the passkey's signature over",
+        "`authenticator_data || sha256(client_data_json)`, and the client data must carry",
+        "the challenge `base64url(sha256(switch || passkey_nonce))`."
+      ],
+      "discriminator": [
+        153,
+        52,
+        69,
+        235,
+        209,
+        22,
+        37,
+        65
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "authenticatorData",
+          "type": "bytes"
+        },
+        {
+          "name": "clientDataJson",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "sendProfileHeartbeat",
+      "docs": [
+        "Send a heartbeat on the profile, extending every linked switch.",
+        "Remaining accounts are the (switch, heartbeat history) pairs of every live switch",
+        "linked to the profile, each of which records the heartbeat."
+      ],
+      "discriminator": [
+        7,
+        145,
+        181,
+        6,
+        72,
+        112,
+        197,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "profile"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCancelTimelock",
+      "docs": [
+        "Require cancellations to be requested `delay_seconds` in advance, giving guardians",
+        "and beneficiaries time to freeze a cancel made with a stolen key. Once set, only",
+        "the delay can be lengthened. Config changes wait out the delay as well."
+      ],
+      "discriminator": [
+        218,
+        30,
+        117,
+        162,
+        242,
+        117,
+        102,
+        226
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "delaySeconds",
+          "type": "i64"
+        },
+        {
+          "name": "guardians",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "freezeQuorum",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setChallengePeriod",
+      "docs": [
+        "Set the window after expiry during which distribution is blocked and the owner can revive.",
+        "Shorthand for proposing a challenge period change, so it is staged behind the config delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        143,
+        103,
+        187,
+        18,
+        71,
+        186,
+        202,
+        101
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "challengePeriodSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setConfigDelay",
+      "docs": [
+        "Set how long config changes wait before they can be applied. Once set, the delay",
+        "can only be lengthened."
+      ],
+      "discriminator": [
+        67,
+        61,
+        39,
+        198,
+        83,
+        106,
+        77,
+        13
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "delaySeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setDuressCode",
+      "docs": [
+        "Register the hash of a duress code, or remove it. Needs guardians from",
+        "`set_cancel_timelock`, since only they can lift the duress state."
+      ],
+      "discriminator": [
+        0,
+        162,
+        252,
+        28,
+        114,
+        244,
+        210,
+        128
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "codeHash",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setEthAddress",
+      "docs": [
+        "Register an Ethereum address allowed to send heartbeats, or remove it"
+      ],
+      "discriminator": [
+        107,
+        51,
+        163,
+        254,
+        142,
+        50,
+        115,
+        56
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "ethAddress",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                20
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setExecutor",
+      "docs": [
+        "Appoint an executor who may adjust shares within `max_share_adjustment_bps`, pay the",
+        "approved expenses and order distributions during a window after the challenge period.",
+        "Passing no executor removes the role. Shorthand for proposing an executor change,",
+        "so it is staged behind the config delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        92,
+        0,
+        234,
+        186,
+        174,
+        14,
+        56,
+        11
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "executor",
+          "type": {
+            "option": "pubkey"
+          }
+        },
+        {
+          "name": "maxShareAdjustmentBps",
+          "type": "u16"
+        },
+        {
+          "name": "executorWindowSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "expenses",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "approvedExpense"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFallback",
+      "docs": [
+        "Set the fallback address that receives unclaimed funds after the claim deadline.",
+        "Shorthand for proposing a fallback change, so it is staged behind the config delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        248,
+        94,
+        224,
+        38,
+        5,
+        235,
+        51,
+        131
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "fallback",
+          "type": "pubkey"
+        },
+        {
+          "name": "claimDeadlineSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setHeartbeatChain",
+      "docs": [
+        "Commit to the tip of a hash chain. From then on heartbeats must reveal the next",
+        "preimage through `send_chained_heartbeat`, so the owner key alone cannot keep the",
+        "switch alive. Set it in the same transaction as the switch is created."
+      ],
+      "discriminator": [
+        132,
+        165,
+        206,
+        115,
+        242,
+        240,
+        147,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "tip",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setHeartbeatSchedule",
+      "docs": [
+        "Switch between a sliding deadline and fixed calendar or aligned windows.",
+        "Shorthand for proposing a schedule change, so it is staged behind the config delay.",
+        "Applied at once, it counts as a heartbeat under the new schedule; a staged schedule",
+        "takes effect from the first heartbeat after it is applied.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        61,
+        158,
+        2,
+        187,
+        52,
+        164,
+        96,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "schedule",
+          "type": {
+            "defined": {
+              "name": "heartbeatSchedule"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setJointOwners",
+      "docs": [
+        "Turn the switch into a joint switch with additional co-owners"
+      ],
+      "discriminator": [
+        136,
+        226,
+        200,
+        103,
+        131,
+        39,
+        179,
+        50
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "coOwners",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "expiryPolicy",
+          "type": {
+            "defined": {
+              "name": "expiryPolicy"
+            }
+          }
+        },
+        {
+          "name": "ownerQuorum",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setMinHeartbeatInterval",
+      "docs": [
+        "Set the minimum time between heartbeats, 0 to disable"
+      ],
+      "discriminator": [
+        118,
+        199,
+        210,
+        217,
+        92,
+        85,
+        180,
+        41
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "minIntervalSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setPasskey",
+      "docs": [
+        "Register the owner's passkey (compressed secp256r1 public key), or remove it"
+      ],
+      "discriminator": [
+        144,
+        207,
+        13,
+        132,
+        79,
+        238,
+        151,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "passkey",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                33
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setWithdrawalLimit",
+      "docs": [
+        "Cap how much `withdraw_partial` can take out per period, or remove the cap.",
+        "Shorthand for proposing a withdrawal limit change: tightening applies at once,",
+        "loosening is staged for a full period or the config delay, whichever is longer.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        97,
+        243,
+        160,
+        126,
+        155,
+        129,
+        70,
+        184
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "limit",
+          "type": {
+            "option": {
+              "defined": {
+                "name": "withdrawalLimit"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "storeExpiryActions",
+      "docs": [
+        "Store instructions to run with the escrow as signer once the switch expires.",
+        "With a config delay or cancel timelock, the actions' hash must first be approved",
+        "through a staged `ConfigChange::ExpiryActions`; storing consumes the approval."
+      ],
+      "discriminator": [
+        236,
+        41,
+        208,
+        29,
+        155,
+        10,
+        39,
+        247
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "expiryActions",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "actions",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "expiryAction"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sweepUnclaimed",
+      "docs": [
+        "Sweep everything left in escrow to the fallback once the claim deadline has passed.",
+        "Remaining accounts are (escrow token account, fallback token account) pairs, one",
+        "of which must be for the switch's mint on an SPL switch, plus the beneficiaries'",
+        "index PDAs. Stored expiry actions are closed along with the switch."
+      ],
+      "discriminator": [
+        64,
+        168,
+        221,
+        224,
+        42,
+        216,
+        138,
+        144
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "account",
+                "path": "switch.switch_id",
+                "account": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "fallback",
+          "writable": true
+        },
+        {
+          "name": "expiryActions",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "thawCancel",
+      "docs": [
+        "Withdraw a freeze vote. The freeze is lifted once fewer than `freeze_quorum`",
+        "votes remain, letting the pending request run or the owner request again."
+      ],
+      "discriminator": [
+        218,
+        157,
+        189,
+        122,
+        43,
+        230,
+        41,
+        134
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voter",
+          "docs": [
+            "Guardian or beneficiary"
+          ],
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "triggerExpiry",
+      "docs": [
+        "Trigger expiry and distribute funds to beneficiaries"
+      ],
+      "discriminator": [
+        152,
+        217,
+        89,
+        33,
+        247,
+        243,
+        133,
+        41
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch.owner",
+                "account": "switch"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "unlinkProfile",
+      "docs": [
+        "Unlink a switch from the profile, restarting its own heartbeat timer"
+      ],
+      "discriminator": [
+        98,
+        86,
+        73,
+        217,
+        238,
+        180,
+        50,
+        252
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "switch",
+            "profile"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "withdrawPartial",
+      "docs": [
+        "Withdraw part of the escrow while the switch stays active. Pass both token accounts",
+        "to withdraw SPL tokens, neither to withdraw SOL. Counts as a heartbeat unless the",
+        "switch is hash-chained or linked to a profile. With a cancel timelock, needs a",
+        "cancel request that has waited out the delay.",
+        "Remaining accounts are co-owner signers when an owner quorum is set."
+      ],
+      "discriminator": [
+        142,
+        181,
+        230,
+        69,
+        132,
+        105,
+        19,
+        229
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "switch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrowTokenAccount",
+          "docs": [
+            "Escrow's token account, for SPL withdrawals"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "ownerTokenAccount",
+          "docs": [
+            "Destination token account, for SPL withdrawals"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "Profile the switch is linked to, if any"
+          ],
+          "optional": true
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawSol",
+      "docs": [
+        "Withdraw SOL after cancellation"
+      ],
+      "discriminator": [
+        145,
+        131,
+        74,
+        136,
+        65,
+        137,
+        42,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "switch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  119,
+                  105,
+                  116,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "switchId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "switch"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "switchId",
+          "type": "string"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "expiryActions",
+      "discriminator": [
+        59,
+        185,
+        235,
+        87,
+        221,
+        33,
+        147,
+        18
+      ]
+    },
+    {
+      "name": "heartbeatHistory",
+      "discriminator": [
+        251,
+        249,
+        43,
+        10,
+        49,
+        134,
+        48,
+        188
+      ]
+    },
+    {
+      "name": "profile",
+      "discriminator": [
+        184,
+        101,
+        165,
+        188,
+        95,
+        63,
+        127,
+        188
+      ]
+    },
+    {
+      "name": "registry",
+      "discriminator": [
+        47,
+        174,
+        110,
+        246,
+        184,
+        182,
+        252,
+        218
+      ]
+    },
+    {
+      "name": "switch",
+      "discriminator": [
+        242,
+        175,
+        214,
+        214,
+        230,
+        53,
+        61,
+        158
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "batchHeartbeatResult",
+      "discriminator": [
+        93,
+        59,
+        168,
+        167,
+        239,
+        76,
+        133,
+        87
+      ]
+    },
+    {
+      "name": "canaryLapsed",
+      "discriminator": [
+        65,
+        138,
+        138,
+        239,
+        113,
+        198,
+        102,
+        133
+      ]
+    },
+    {
+      "name": "canaryPublished",
+      "discriminator": [
+        206,
+        65,
+        114,
+        145,
+        40,
+        237,
+        34,
+        244
+      ]
+    },
+    {
+      "name": "cancelFrozen",
+      "discriminator": [
+        252,
+        204,
+        193,
+        228,
+        18,
+        238,
+        80,
+        1
+      ]
+    },
+    {
+      "name": "cancelRequested",
+      "discriminator": [
+        178,
+        234,
+        154,
+        172,
+        145,
+        115,
+        28,
+        116
+      ]
+    },
+    {
+      "name": "cancelThawed",
+      "discriminator": [
+        244,
+        233,
+        93,
+        127,
+        250,
+        230,
+        184,
+        119
+      ]
+    },
+    {
+      "name": "configChangeApplied",
+      "discriminator": [
+        65,
+        124,
+        47,
+        248,
+        1,
+        25,
+        230,
+        154
+      ]
+    },
+    {
+      "name": "configChangeCanceled",
+      "discriminator": [
+        149,
+        253,
+        219,
+        246,
+        91,
+        131,
+        234,
+        76
+      ]
+    },
+    {
+      "name": "configChangeProposed",
+      "discriminator": [
+        57,
+        212,
+        96,
+        3,
+        9,
+        29,
+        225,
+        198
+      ]
+    },
+    {
+      "name": "duressCleared",
+      "discriminator": [
+        91,
+        79,
+        239,
+        21,
+        13,
+        49,
+        114,
+        213
+      ]
+    },
+    {
+      "name": "duressSignaled",
+      "discriminator": [
+        195,
+        31,
+        85,
+        81,
+        94,
+        60,
+        197,
+        42
+      ]
+    },
+    {
+      "name": "livenessChallenged",
+      "discriminator": [
+        49,
+        255,
+        89,
+        155,
+        32,
+        54,
+        166,
+        57
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidSwitchId",
+      "msg": "Invalid switch ID (1-32 characters, not starting with '#')"
+    },
+    {
+      "code": 6001,
+      "name": "invalidBeneficiaryCount",
+      "msg": "Invalid number of beneficiaries (1-10 allowed)"
+    },
+    {
+      "code": 6002,
+      "name": "invalidShareDistribution",
+      "msg": "Beneficiary shares must sum to 10000 basis points (100%)"
+    },
+    {
+      "code": 6003,
+      "name": "invalidTimeout",
+      "msg": "Timeout must be positive"
+    },
+    {
+      "code": 6004,
+      "name": "switchNotActive",
+      "msg": "Switch is not active"
+    },
+    {
+      "code": 6005,
+      "name": "deadlineNotPassed",
+      "msg": "Deadline has not passed yet"
+    },
+    {
+      "code": 6006,
+      "name": "switchNotExpired",
+      "msg": "Switch has not expired"
+    },
+    {
+      "code": 6007,
+      "name": "switchNotCanceled",
+      "msg": "Switch has not been canceled"
+    },
+    {
+      "code": 6008,
+      "name": "switchAlreadyExpired",
+      "msg": "Switch has already expired - heartbeat rejected"
+    },
+    {
+      "code": 6009,
+      "name": "invalidTokenType",
+      "msg": "Invalid token type for this operation"
+    },
+    {
+      "code": 6010,
+      "name": "insufficientFunds",
+      "msg": "Insufficient funds in escrow"
+    },
+    {
+      "code": 6011,
+      "name": "beneficiaryNotFound",
+      "msg": "Beneficiary not found"
+    },
+    {
+      "code": 6012,
+      "name": "invalidAssetAllocation",
+      "msg": "Invalid asset allocation - beneficiary must have at least one asset"
+    },
+    {
+      "code": 6013,
+      "name": "invalidClaimDeadline",
+      "msg": "Claim deadline must be positive"
+    },
+    {
+      "code": 6014,
+      "name": "fallbackNotSet",
+      "msg": "No fallback address has been set"
+    },
+    {
+      "code": 6015,
+      "name": "invalidFallback",
+      "msg": "Account does not match the switch fallback"
+    },
+    {
+      "code": 6016,
+      "name": "claimDeadlineNotPassed",
+      "msg": "Claim deadline has not passed yet"
+    },
+    {
+      "code": 6017,
+      "name": "invalidEscrowTokenAccount",
+      "msg": "Invalid escrow or fallback token account"
+    },
+    {
+      "code": 6018,
+      "name": "invalidChallengePeriod",
+      "msg": "Challenge period must be between 0 and 90 days"
+    },
+    {
+      "code": 6019,
+      "name": "challengePeriodActive",
+      "msg": "Distribution is blocked during the challenge period"
+    },
+    {
+      "code": 6020,
+      "name": "challengePeriodEnded",
+      "msg": "Challenge period has ended - switch can no longer be revived"
+    },
+    {
+      "code": 6021,
+      "name": "invalidCoOwners",
+      "msg": "Invalid co-owners (1-5 unique co-owners, set once)"
+    },
+    {
+      "code": 6022,
+      "name": "invalidExpiryPolicy",
+      "msg": "Expiry threshold must be between 1 and the number of owners"
+    },
+    {
+      "code": 6023,
+      "name": "invalidOwnerQuorum",
+      "msg": "Owner quorum must be between 1 and the number of owners"
+    },
+    {
+      "code": 6024,
+      "name": "notCoOwner",
+      "msg": "Signer is not a co-owner of this switch"
+    },
+    {
+      "code": 6025,
+      "name": "ownerQuorumNotMet",
+      "msg": "Not enough owner signatures"
+    },
+    {
+      "code": 6026,
+      "name": "stakeAccountAlreadyRegistered",
+      "msg": "Stake account is already registered on this switch"
+    },
+    {
+      "code": 6027,
+      "name": "tooManyStakeAccounts",
+      "msg": "Too many stake accounts (max 5)"
+    },
+    {
+      "code": 6028,
+      "name": "stakeAccountNotFound",
+      "msg": "Stake account is not registered on this switch"
+    },
+    {
+      "code": 6029,
+      "name": "stakeAccountsRegistered",
+      "msg": "Registered stake accounts must be reclaimed or released first"
+    },
+    {
+      "code": 6030,
+      "name": "invalidAuthorityTarget",
+      "msg": "Target account does not match the authority target"
+    },
+    {
+      "code": 6031,
+      "name": "authorityAlreadyHeld",
+      "msg": "Authority is already held by this switch"
+    },
+    {
+      "code": 6032,
+      "name": "tooManyAuthorities",
+      "msg": "Too many held authorities (max 5)"
+    },
+    {
+      "code": 6033,
+      "name": "authorityNotFound",
+      "msg": "Authority is not held by this switch"
+    },
+    {
+      "code": 6034,
+      "name": "authoritiesHeld",
+      "msg": "Held authorities must be reclaimed or released first"
+    },
+    {
+      "code": 6035,
+      "name": "invalidExpiryAction",
+      "msg": "Invalid expiry action (1-4 actions, max 8 accounts and 256 bytes of data)"
+    },
+    {
+      "code": 6036,
+      "name": "invalidActionSigner",
+      "msg": "Only the escrow may sign expiry actions"
+    },
+    {
+      "code": 6037,
+      "name": "expiryActionAlreadyExecuted",
+      "msg": "Expiry action has already been executed"
+    },
+    {
+      "code": 6038,
+      "name": "profileExpired",
+      "msg": "Profile has expired - heartbeat rejected"
+    },
+    {
+      "code": 6039,
+      "name": "switchLinkedToProfile",
+      "msg": "Switch is linked to a profile - send the heartbeat to the profile"
+    },
+    {
+      "code": 6040,
+      "name": "profileRequired",
+      "msg": "Switch is linked to a profile that was not provided"
+    },
+    {
+      "code": 6041,
+      "name": "profileMismatch",
+      "msg": "Profile does not match the one linked to the switch"
+    },
+    {
+      "code": 6042,
+      "name": "invalidSwitchAccount",
+      "msg": "Account is not a valid switch PDA"
+    },
+    {
+      "code": 6043,
+      "name": "notSwitchOwner",
+      "msg": "Signer does not own this switch"
+    },
+    {
+      "code": 6044,
+      "name": "invalidLabel",
+      "msg": "Label is too long (max 64 characters)"
+    },
+    {
+      "code": 6045,
+      "name": "beneficiaryIndexMissing",
+      "msg": "Beneficiary index account missing from remaining accounts"
+    },
+    {
+      "code": 6046,
+      "name": "invalidHeartbeatSchedule",
+      "msg": "Invalid heartbeat schedule (1-12 months, or a positive interval with an offset inside it)"
+    },
+    {
+      "code": 6047,
+      "name": "invalidHeartbeatInterval",
+      "msg": "Minimum heartbeat interval must be non-negative and shorter than the timeout"
+    },
+    {
+      "code": 6048,
+      "name": "heartbeatTooSoon",
+      "msg": "Heartbeat sent before the minimum interval has elapsed"
+    },
+    {
+      "code": 6049,
+      "name": "invalidHeartbeatHistory",
+      "msg": "Heartbeat history account does not belong to the switch"
+    },
+    {
+      "code": 6050,
+      "name": "livenessChallengePending",
+      "msg": "A liveness challenge is already waiting for the owner"
+    },
+    {
+      "code": 6051,
+      "name": "livenessChallengeCooldown",
+      "msg": "Beneficiary challenged liveness too recently"
+    },
+    {
+      "code": 6052,
+      "name": "invalidExecutorConfig",
+      "msg": "Invalid executor configuration"
+    },
+    {
+      "code": 6053,
+      "name": "notExecutor",
+      "msg": "Signer is not the switch executor"
+    },
+    {
+      "code": 6054,
+      "name": "executorWindowClosed",
+      "msg": "Executor window is closed"
+    },
+    {
+      "code": 6055,
+      "name": "executorWindowOpen",
+      "msg": "Distribution is held until the executor window closes"
+    },
+    {
+      "code": 6056,
+      "name": "invalidShareAdjustment",
+      "msg": "Share adjustments must cover every beneficiary, net to zero and stay within bounds"
+    },
+    {
+      "code": 6057,
+      "name": "expenseNotApproved",
+      "msg": "Payee is not an approved expense"
+    },
+    {
+      "code": 6058,
+      "name": "expenseCapExceeded",
+      "msg": "Expense payment exceeds its cap"
+    },
+    {
+      "code": 6059,
+      "name": "invalidDistributionOrder",
+      "msg": "Distribution order must list every beneficiary exactly once"
+    },
+    {
+      "code": 6060,
+      "name": "distributionOutOfOrder",
+      "msg": "Beneficiary is not next in the executor's distribution order"
+    },
+    {
+      "code": 6061,
+      "name": "invalidWithdrawalLimit",
+      "msg": "Withdrawal period must be positive"
+    },
+    {
+      "code": 6062,
+      "name": "withdrawalLimitExceeded",
+      "msg": "Withdrawal exceeds the limit for this period"
+    },
+    {
+      "code": 6063,
+      "name": "invalidCancelTimelock",
+      "msg": "Invalid cancel timelock (positive delay, up to 5 unique guardians, reachable freeze quorum)"
+    },
+    {
+      "code": 6064,
+      "name": "cancelTimelockLocked",
+      "msg": "Cancel timelock can only be lengthened once set"
+    },
+    {
+      "code": 6065,
+      "name": "cancelAlreadyRequested",
+      "msg": "A cancel is already pending"
+    },
+    {
+      "code": 6066,
+      "name": "cancelNotRequested",
+      "msg": "No cancel has been requested"
+    },
+    {
+      "code": 6067,
+      "name": "cancelTimelockActive",
+      "msg": "Cancel timelock has not elapsed yet"
+    },
+    {
+      "code": 6068,
+      "name": "cancelFrozen",
+      "msg": "Pending cancel was frozen by guardians and beneficiaries"
+    },
+    {
+      "code": 6069,
+      "name": "notGuardianOrBeneficiary",
+      "msg": "Signer is neither a guardian nor a beneficiary"
+    },
+    {
+      "code": 6070,
+      "name": "freezeAlreadyVoted",
+      "msg": "Voter has already voted to freeze this cancel"
+    },
+    {
+      "code": 6071,
+      "name": "invalidConfigDelay",
+      "msg": "Config change delay cannot be negative"
+    },
+    {
+      "code": 6072,
+      "name": "configDelayLocked",
+      "msg": "Config change delay can only be lengthened"
+    },
+    {
+      "code": 6073,
+      "name": "configChangePending",
+      "msg": "A config change is already pending"
+    },
+    {
+      "code": 6074,
+      "name": "noPendingConfigChange",
+      "msg": "No config change is pending"
+    },
+    {
+      "code": 6075,
+      "name": "configChangeDelayActive",
+      "msg": "Config change delay has not elapsed yet"
+    },
+    {
+      "code": 6076,
+      "name": "heartbeatPreimageRequired",
+      "msg": "Switch uses a heartbeat hash chain - reveal the next preimage"
+    },
+    {
+      "code": 6077,
+      "name": "invalidHeartbeatPreimage",
+      "msg": "Preimage does not hash to the committed chain value"
+    },
+    {
+      "code": 6078,
+      "name": "heartbeatChainAlreadySet",
+      "msg": "Heartbeat chain is already set - re-seed it with a preimage"
+    },
+    {
+      "code": 6079,
+      "name": "heartbeatChainNotSet",
+      "msg": "Switch does not use a heartbeat hash chain"
+    },
+    {
+      "code": 6080,
+      "name": "duressRequiresGuardians",
+      "msg": "A duress code needs guardians to clear the duress state"
+    },
+    {
+      "code": 6081,
+      "name": "switchNotInDuress",
+      "msg": "Switch is not under duress"
+    },
+    {
+      "code": 6082,
+      "name": "notGuardian",
+      "msg": "Signer is not a guardian of this switch"
+    },
+    {
+      "code": 6083,
+      "name": "duressClearAlreadyVoted",
+      "msg": "Guardian has already voted to clear this duress"
+    },
+    {
+      "code": 6084,
+      "name": "invalidCanaryUri",
+      "msg": "Canary URI is too long (max 128 characters)"
+    },
+    {
+      "code": 6085,
+      "name": "notCanary",
+      "msg": "Switch is not a warrant canary"
+    },
+    {
+      "code": 6086,
+      "name": "notForCanary",
+      "msg": "Not available for warrant canaries"
+    },
+    {
+      "code": 6087,
+      "name": "canaryLapsed",
+      "msg": "Canary has lapsed and cannot be revived"
+    },
+    {
+      "code": 6088,
+      "name": "passkeyNotSet",
+      "msg": "No passkey is registered on this switch"
+    },
+    {
+      "code": 6089,
+      "name": "missingSignatureVerification",
+      "msg": "Signature verification instruction must immediately precede this one"
+    },
+    {
+      "code": 6090,
+      "name": "invalidSignatureVerification",
+      "msg": "Signature verification does not cover the expected key and message"
+    },
+    {
+      "code": 6091,
+      "name": "invalidPasskeyAssertion",
+      "msg": "Passkey assertion does not match the switch challenge"
+    },
+    {
+      "code": 6092,
+      "name": "ethAddressNotSet",
+      "msg": "No Ethereum address is registered on this switch"
+    },
+    {
+      "code": 6093,
+      "name": "invalidEthHeartbeatMessage",
+      "msg": "Signed message is not the expected heartbeat for this switch and nonce"
+    },
+    {
+      "code": 6094,
+      "name": "freezeVoteNotFound",
+      "msg": "Voter has no freeze vote to withdraw"
+    },
+    {
+      "code": 6095,
+      "name": "expiryActionsNotApproved",
+      "msg": "Expiry actions do not match a hash approved through a config change"
+    },
+    {
+      "code": 6096,
+      "name": "linkedSwitchesRequired",
+      "msg": "Profile heartbeat must include every live linked switch exactly once"
+    },
+    {
+      "code": 6097,
+      "name": "sweepTokenAccountRequired",
+      "msg": "Sweeping an SPL switch must include the escrow's token account for its mint"
+    },
+    {
+      "code": 6098,
+      "name": "jointSwitchNotChainable",
+      "msg": "Joint switches cannot use a heartbeat chain"
+    }
+  ],
+  "types": [
+    {
+      "name": "actionAccountMeta",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "isSigner",
+            "type": "bool"
+          },
+          {
+            "name": "isWritable",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "approvedExpense",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payee",
+            "type": "pubkey"
+          },
+          {
+            "name": "capLamports",
+            "type": "u64"
+          },
+          {
+            "name": "paidLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "assetAllocation",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetType",
+            "type": {
+              "defined": {
+                "name": "assetType"
+              }
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "assetType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "sol"
+          },
+          {
+            "name": "splToken",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "pubkey"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "authorityKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "programUpgrade"
+          },
+          {
+            "name": "mintTokens"
+          },
+          {
+            "name": "freezeAccount"
+          }
+        ]
+      }
+    },
+    {
+      "name": "batchHeartbeatResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "switchId",
+            "type": "string"
+          },
+          {
+            "name": "success",
+            "type": "bool"
+          },
+          {
+            "name": "heartbeatDeadline",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "beneficiary",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "pubkey"
+          },
+          {
+            "name": "shareBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "beneficiaryAllocation",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "pubkey"
+          },
+          {
+            "name": "assets",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "assetAllocation"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "beneficiaryEntitlement",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "pubkey"
+          },
+          {
+            "name": "shareBps",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "canary",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statementHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "publishedAt",
+            "type": "i64"
+          },
+          {
+            "name": "lapsedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "canaryLapsed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "statementHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "lapsedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "canaryPublished",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "statementHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cancelFrozen",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "votes",
+            "type": {
+              "vec": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "cancelRequested",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "requestedAt",
+            "type": "i64"
+          },
+          {
+            "name": "executableAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cancelThawed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "votes",
+            "type": {
+              "vec": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "coOwner",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "type": "pubkey"
+          },
+          {
+            "name": "lastHeartbeatAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "configChange",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "beneficiaries",
+            "fields": [
+              {
+                "name": "beneficiaries",
+                "type": {
+                  "vec": {
+                    "defined": {
+                      "name": "beneficiary"
+                    }
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "timeout",
+            "fields": [
+              {
+                "name": "timeoutSeconds",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "fallback",
+            "fields": [
+              {
+                "name": "fallback",
+                "type": "pubkey"
+              },
+              {
+                "name": "claimDeadlineSeconds",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "heartbeatSchedule",
+            "fields": [
+              {
+                "name": "schedule",
+                "type": {
+                  "defined": {
+                    "name": "heartbeatSchedule"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "challengePeriod",
+            "fields": [
+              {
+                "name": "challengePeriodSeconds",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "executor",
+            "fields": [
+              {
+                "name": "executor",
+                "type": {
+                  "option": "pubkey"
+                }
+              },
+              {
+                "name": "maxShareAdjustmentBps",
+                "type": "u16"
+              },
+              {
+                "name": "executorWindowSeconds",
+                "type": "i64"
+              },
+              {
+                "name": "expenses",
+                "type": {
+                  "vec": {
+                    "defined": {
+                      "name": "approvedExpense"
+                    }
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "expiryActions",
+            "fields": [
+              {
+                "name": "actionsHash",
+                "type": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            ]
+          },
+          {
+            "name": "withdrawalLimit",
+            "fields": [
+              {
+                "name": "limit",
+                "type": {
+                  "option": {
+                    "defined": {
+                      "name": "withdrawalLimit"
+                    }
+                  }
+                }
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "configChangeApplied",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "configChange"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "configChangeCanceled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "configChange"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "configChangeProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "configChange"
+              }
+            }
+          },
+          {
+            "name": "activatesAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duressCleared",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "votes",
+            "type": {
+              "vec": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "duressSignaled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "signaledAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "expiryAction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "programId",
+            "type": "pubkey"
+          },
+          {
+            "name": "accounts",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "actionAccountMeta"
+                }
+              }
+            }
+          },
+          {
+            "name": "data",
+            "type": "bytes"
+          },
+          {
+            "name": "executed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "expiryActions",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "actions",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "expiryAction"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "expiryPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "any"
+          },
+          {
+            "name": "all"
+          },
+          {
+            "name": "threshold",
+            "fields": [
+              {
+                "name": "missed",
+                "type": "u8"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "heartbeatHistory",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "records",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "heartbeatRecord"
+                }
+              }
+            }
+          },
+          {
+            "name": "nextIndex",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "heartbeatMethod",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "direct"
+          },
+          {
+            "name": "signedMessage"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "switchNotActive",
-      "msg": "Switch is not active"
+      "name": "heartbeatRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "timestamp",
+            "type": "i64"
+          },
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "signer",
+            "type": "pubkey"
+          },
+          {
+            "name": "method",
+            "type": {
+              "defined": {
+                "name": "heartbeatMethod"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "deadlineNotPassed",
-      "msg": "Deadline has not passed yet"
+      "name": "heartbeatSchedule",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "sliding"
+          },
+          {
+            "name": "fixedPeriod",
+            "fields": [
+              {
+                "name": "months",
+                "type": "u8"
+              }
+            ]
+          },
+          {
+            "name": "alignedInterval",
+            "fields": [
+              {
+                "name": "intervalSeconds",
+                "type": "i64"
+              },
+              {
+                "name": "offsetSeconds",
+                "type": "i64"
+              }
+            ]
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "switchNotExpired",
-      "msg": "Switch has not expired"
+      "name": "heldAuthority",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": {
+              "defined": {
+                "name": "authorityKind"
+              }
+            }
+          },
+          {
+            "name": "target",
+            "type": "pubkey"
+          },
+          {
+            "name": "beneficiary",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "switchNotCanceled",
-      "msg": "Switch has not been canceled"
+      "name": "livenessChallenge",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beneficiary",
+            "type": "pubkey"
+          },
+          {
+            "name": "challengedAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "switchAlreadyExpired",
-      "msg": "Switch has already expired - heartbeat rejected"
+      "name": "livenessChallenged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "switch",
+            "type": "pubkey"
+          },
+          {
+            "name": "beneficiary",
+            "type": "pubkey"
+          },
+          {
+            "name": "deadline",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "invalidTokenType",
-      "msg": "Invalid token type for this operation"
+      "name": "pendingConfigChange",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "configChange"
+              }
+            }
+          },
+          {
+            "name": "proposedAt",
+            "type": "i64"
+          },
+          {
+            "name": "activatesAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "insufficientFunds",
-      "msg": "Insufficient funds in escrow"
+      "name": "profile",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "timeoutSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "heartbeatDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "linkedSwitches",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "beneficiaryNotFound",
-      "msg": "Beneficiary not found"
+      "name": "registry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "switchCount",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "invalidAssetAllocation",
-      "msg": "Invalid asset allocation - beneficiary must have at least one asset"
-    }
-  ],
-  "types": [
+      "name": "stakeInheritance",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "stakeAccount",
+            "type": "pubkey"
+          },
+          {
+            "name": "beneficiary",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
     {
-      "name": "assetAllocation",
+      "name": "switch",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetType",
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "switchId",
+            "type": "string"
+          },
+          {
+            "name": "beneficiaries",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "beneficiary"
+                }
+              }
+            }
+          },
+          {
+            "name": "tokenType",
+            "type": {
+              "defined": {
+                "name": "tokenType"
+              }
+            }
+          },
+          {
+            "name": "timeoutSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "heartbeatDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "switchStatus"
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "fallback",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "claimDeadlineSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "expiredAt",
+            "type": "i64"
+          },
+          {
+            "name": "challengePeriodSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "coOwners",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "coOwner"
+                }
+              }
+            }
+          },
+          {
+            "name": "expiryPolicy",
+            "type": {
+              "defined": {
+                "name": "expiryPolicy"
+              }
+            }
+          },
+          {
+            "name": "ownerQuorum",
+            "type": "u8"
+          },
+          {
+            "name": "stakeAccounts",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "stakeInheritance"
+                }
+              }
+            }
+          },
+          {
+            "name": "heldAuthorities",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "heldAuthority"
+                }
+              }
+            }
+          },
+          {
+            "name": "profile",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "label",
+            "type": "string"
+          },
+          {
+            "name": "heartbeatSchedule",
             "type": {
               "defined": {
-                "name": "assetType"
+                "name": "heartbeatSchedule"
               }
             }
           },
           {
-            "name": "amount",
+            "name": "lastHeartbeatAt",
+            "type": "i64"
+          },
+          {
+            "name": "heartbeatCount",
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "assetType",
-      "type": {
-        "kind": "enum",
-        "variants": [
+          },
           {
-            "name": "sol"
+            "name": "minHeartbeatIntervalSeconds",
+            "type": "i64"
           },
           {
-            "name": "splToken",
-            "fields": [
-              {
-                "name": "mint",
-                "type": "pubkey"
+            "name": "livenessChallengedAt",
+            "type": "i64"
+          },
+          {
+            "name": "livenessChallenges",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "livenessChallenge"
+                }
               }
-            ]
-          }
-        ]
-      }
-    },
-    {
-      "name": "beneficiary",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            }
+          },
           {
-            "name": "address",
-            "type": "pubkey"
+            "name": "executor",
+            "type": {
+              "option": "pubkey"
+            }
           },
           {
-            "name": "shareBps",
+            "name": "maxShareAdjustmentBps",
             "type": "u16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "beneficiaryAllocation",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "address",
-            "type": "pubkey"
+            "name": "executorWindowSeconds",
+            "type": "i64"
           },
           {
-            "name": "assets",
+            "name": "approvedExpenses",
             "type": {
               "vec": {
                 "defined": {
-                  "name": "assetAllocation"
+                  "name": "approvedExpense"
                 }
               }
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "switch",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "owner",
-            "type": "pubkey"
+            "name": "shareAdjustments",
+            "type": {
+              "vec": "i16"
+            }
           },
           {
-            "name": "switchId",
-            "type": "string"
+            "name": "distributionOrder",
+            "type": "bytes"
           },
           {
-            "name": "beneficiaries",
+            "name": "distributionsMade",
+            "type": "u8"
+          },
+          {
+            "name": "executorFinalized",
+            "type": "bool"
+          },
+          {
+            "name": "withdrawalLimit",
             "type": {
-              "vec": {
+              "option": {
                 "defined": {
-                  "name": "beneficiary"
+                  "name": "withdrawalLimit"
                 }
               }
             }
           },
           {
-            "name": "tokenType",
+            "name": "withdrawalPeriodStart",
+            "type": "i64"
+          },
+          {
+            "name": "withdrawnLamports",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawnTokens",
+            "type": "u64"
+          },
+          {
+            "name": "cancelDelaySeconds",
+            "type": "i64"
+          },
+          {
+            "name": "guardians",
             "type": {
-              "defined": {
-                "name": "tokenType"
-              }
+              "vec": "pubkey"
             }
           },
           {
-            "name": "timeoutSeconds",
+            "name": "freezeQuorum",
+            "type": "u8"
+          },
+          {
+            "name": "cancelRequestedAt",
             "type": "i64"
           },
           {
-            "name": "heartbeatDeadline",
+            "name": "freezeVotes",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "cancelFrozen",
+            "type": "bool"
+          },
+          {
+            "name": "configDelaySeconds",
             "type": "i64"
           },
           {
-            "name": "status",
+            "name": "pendingConfigChange",
             "type": {
-              "defined": {
-                "name": "switchStatus"
+              "option": {
+                "defined": {
+                  "name": "pendingConfigChange"
+                }
               }
             }
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "heartbeatChain",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "duressCodeHash",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "duressAt",
+            "type": "i64"
+          },
+          {
+            "name": "duressClearVotes",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "canary",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "canary"
+                }
+              }
+            }
+          },
+          {
+            "name": "passkey",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  33
+                ]
+              }
+            }
+          },
+          {
+            "name": "passkeyNonce",
+            "type": "u64"
+          },
+          {
+            "name": "ethAddress",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  20
+                ]
+              }
+            }
+          },
+          {
+            "name": "ethNonce",
+            "type": "u64"
+          },
+          {
+            "name": "expiryActionsHash",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           }
         ]
       }
@@ -991,6 +7352,59 @@ export type Deadmansswitch = {
           },
           {
             "name": "canceled"
+          },
+          {
+            "name": "duress"
+          }
+        ]
+      }
+    },
+    {
+      "name": "switchStatusView",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "switchStatus"
+              }
+            }
+          },
+          {
+            "name": "heartbeatDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "secondsRemaining",
+            "type": "i64"
+          },
+          {
+            "name": "tokenType",
+            "type": {
+              "defined": {
+                "name": "tokenType"
+              }
+            }
+          },
+          {
+            "name": "entitlements",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "beneficiaryEntitlement"
+                }
+              }
+            }
+          },
+          {
+            "name": "lastHeartbeatAt",
+            "type": "i64"
+          },
+          {
+            "name": "heartbeatCount",
+            "type": "u64"
           }
         ]
       }
@@ -1014,6 +7428,26 @@ export type Deadmansswitch = {
           }
         ]
       }
+    },
+    {
+      "name": "withdrawalLimit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lamportsPerPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "tokensPerPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "periodSeconds",
+            "type": "i64"
+          }
+        ]
+      }
     }
   ]
 };
@@ -1,27 +1,68 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use anchor_lang::system_program::{transfer, Transfer as SystemTransfer};
+use anchor_spl::stake::{self, Authorize, Stake, StakeAccount};
+use anchor_spl::token::{
+    self, spl_token::instruction::AuthorityType, CloseAccount, SetAuthority, Token, TokenAccount,
+    Transfer,
+};
+#[allow(deprecated)] // Still the only upgradeable-loader helpers exported by anchor-lang 0.31.
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::{
+    hash::hash,
+    secp256k1_program,
+    instruction::Instruction,
+    program::invoke_signed,
+    pubkey,
+    stake::state::StakeAuthorize,
+    sysvar::instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount,
+    Transfer as SystemTransfer,
+};
 
 declare_id!("BUE3LbNV3jkqGwE1E1ouvka3pcHuDvpLw4u9WT8oexxr");
 
 const MAX_BENEFICIARIES: usize = 10;
 const BASIS_POINTS_TOTAL: u16 = 10000; // 100.00%
 const MAX_SWITCH_ID_LEN: usize = 32;
+const MAX_LABEL_LEN: usize = 64;
+const INDEXED_SWITCH_ID_PREFIX: char = '#'; // Reserved for registry-indexed switch IDs
+const MAX_CO_OWNERS: usize = 5;
+const MAX_STAKE_ACCOUNTS: usize = 5;
+const MAX_HELD_AUTHORITIES: usize = 5;
+const MAX_EXPIRY_ACTIONS: usize = 4;
+const MAX_ACTION_ACCOUNTS: usize = 8;
+const MAX_ACTION_DATA_LEN: usize = 256;
+const SECONDS_PER_DAY: i64 = 86_400;
+const HEARTBEAT_HISTORY_LEN: usize = 16;
+const LIVENESS_CHALLENGE_SECONDS: i64 = 14 * SECONDS_PER_DAY;
+const LIVENESS_CHALLENGE_COOLDOWN_SECONDS: i64 = 90 * SECONDS_PER_DAY;
+const MAX_CHALLENGE_PERIOD_SECONDS: i64 = 90 * SECONDS_PER_DAY;
+const MAX_EXECUTOR_WINDOW_SECONDS: i64 = 365 * SECONDS_PER_DAY;
+const MAX_APPROVED_EXPENSES: usize = 5;
+const MAX_GUARDIANS: usize = 5;
+const MAX_SCHEDULE_MONTHS: u8 = 12;
+const MAX_CANARY_URI_LEN: usize = 128;
+const SECP256R1_PROGRAM_ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+const WEBAUTHN_USER_PRESENT: u8 = 0x01; // authenticatorData flags bit
 
 #[program]
 pub mod deadmansswitch {
     use super::*;
 
     /// Initialize a new Dead Man's Switch with a unique ID
-    pub fn initialize_switch(
-        ctx: Context<InitializeSwitch>,
+    /// Remaining accounts are the beneficiaries' index PDAs.
+    pub fn initialize_switch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeSwitch<'info>>,
         switch_id: String,
         timeout_seconds: i64,
         beneficiaries: Vec<Beneficiary>,
         token_type: TokenType,
     ) -> Result<()> {
         require!(
-            switch_id.len() > 0 && switch_id.len() <= MAX_SWITCH_ID_LEN,
+            switch_id.len() > 0
+                && switch_id.len() <= MAX_SWITCH_ID_LEN
+                && !switch_id.starts_with(INDEXED_SWITCH_ID_PREFIX),
             ErrorCode::InvalidSwitchId
         );
         
@@ -48,9 +89,22 @@ pub mod deadmansswitch {
         switch.token_type = token_type;
         switch.timeout_seconds = timeout_seconds;
         switch.heartbeat_deadline = clock.unix_timestamp + timeout_seconds;
+        switch.last_heartbeat_at = clock.unix_timestamp;
         switch.status = SwitchStatus::Active;
         switch.bump = ctx.bumps.switch;
 
+        let history = &mut ctx.accounts.history;
+        history.switch = switch.key();
+        history.bump = ctx.bumps.history;
+
+        add_to_beneficiary_indexes(
+            switch.key(),
+            &switch.beneficiaries,
+            ctx.remaining_accounts,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
         msg!(
             "Switch '{}' initialized. Deadline: {}",
             switch_id,
@@ -61,14 +115,17 @@ pub mod deadmansswitch {
     }
 
     /// Initialize a switch with specific asset allocations (enhanced version)
-    pub fn initialize_switch_with_assets(
-        ctx: Context<InitializeSwitch>,
+    /// Remaining accounts are the beneficiaries' index PDAs.
+    pub fn initialize_switch_with_assets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeSwitch<'info>>,
         switch_id: String,
         timeout_seconds: i64,
         allocations: Vec<BeneficiaryAllocation>,
     ) -> Result<()> {
         require!(
-            switch_id.len() > 0 && switch_id.len() <= MAX_SWITCH_ID_LEN,
+            switch_id.len() > 0
+                && switch_id.len() <= MAX_SWITCH_ID_LEN
+                && !switch_id.starts_with(INDEXED_SWITCH_ID_PREFIX),
             ErrorCode::InvalidSwitchId
         );
         
@@ -100,9 +157,22 @@ pub mod deadmansswitch {
         switch.token_type = TokenType::Sol; // Default, actual types stored in allocations
         switch.timeout_seconds = timeout_seconds;
         switch.heartbeat_deadline = clock.unix_timestamp + timeout_seconds;
+        switch.last_heartbeat_at = clock.unix_timestamp;
         switch.status = SwitchStatus::Active;
         switch.bump = ctx.bumps.switch;
 
+        let history = &mut ctx.accounts.history;
+        history.switch = switch.key();
+        history.bump = ctx.bumps.history;
+
+        add_to_beneficiary_indexes(
+            switch.key(),
+            &switch.beneficiaries,
+            ctx.remaining_accounts,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
         msg!(
             "Switch '{}' with asset allocations initialized. {} beneficiaries. Deadline: {}",
             switch_id,
@@ -113,259 +183,305 @@ pub mod deadmansswitch {
         Ok(())
     }
 
-    /// Send a heartbeat to extend the deadline
-    pub fn send_heartbeat(ctx: Context<SendHeartbeat>, _switch_id: String) -> Result<()> {
+    /// Initialize a fund-free warrant canary: a switch without beneficiaries that publishes
+    /// a statement hash and URI and is renewed by heartbeat. Once expired it is flagged
+    /// as lapsed for good.
+    pub fn initialize_canary(
+        ctx: Context<InitializeSwitch>,
+        switch_id: String,
+        timeout_seconds: i64,
+        statement_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        require!(
+            !switch_id.is_empty()
+                && switch_id.len() <= MAX_SWITCH_ID_LEN
+                && !switch_id.starts_with(INDEXED_SWITCH_ID_PREFIX),
+            ErrorCode::InvalidSwitchId
+        );
+
+        require!(timeout_seconds > 0, ErrorCode::InvalidTimeout);
+
+        require!(uri.len() <= MAX_CANARY_URI_LEN, ErrorCode::InvalidCanaryUri);
+
         let switch = &mut ctx.accounts.switch;
         let clock = Clock::get()?;
 
-        require!(
-            switch.status == SwitchStatus::Active,
-            ErrorCode::SwitchNotActive
-        );
+        switch.owner = ctx.accounts.owner.key();
+        switch.switch_id = switch_id.clone();
+        switch.token_type = TokenType::Sol;
+        switch.timeout_seconds = timeout_seconds;
+        switch.heartbeat_deadline = clock.unix_timestamp + timeout_seconds;
+        switch.last_heartbeat_at = clock.unix_timestamp;
+        switch.status = SwitchStatus::Active;
+        switch.bump = ctx.bumps.switch;
+        switch.canary = Some(Canary {
+            statement_hash,
+            uri: uri.clone(),
+            published_at: clock.unix_timestamp,
+            lapsed_at: 0,
+        });
 
-        // Check if deadline has already passed - if so, mark as expired and reject heartbeat
-        require!(
-            clock.unix_timestamp <= switch.heartbeat_deadline,
-            ErrorCode::SwitchAlreadyExpired
-        );
+        let history = &mut ctx.accounts.history;
+        history.switch = switch.key();
+        history.bump = ctx.bumps.history;
 
-        // Update deadline only if switch is still active and not expired
-        switch.heartbeat_deadline = clock.unix_timestamp + switch.timeout_seconds;
+        emit!(CanaryPublished {
+            switch: switch.key(),
+            statement_hash,
+            uri,
+        });
 
-        msg!("Heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+        msg!(
+            "Canary '{}' initialized. Deadline: {}",
+            switch_id,
+            switch.heartbeat_deadline
+        );
 
         Ok(())
     }
 
-    /// Trigger expiry and distribute funds to beneficiaries
-    pub fn trigger_expiry(ctx: Context<TriggerExpiry>, _switch_id: String) -> Result<()> {
+    /// Publish a new canary statement, which also renews the canary
+    pub fn publish_canary(
+        ctx: Context<SendHeartbeat>,
+        _switch_id: String,
+        statement_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
         let switch = &mut ctx.accounts.switch;
         let clock = Clock::get()?;
 
-        require!(
-            switch.status == SwitchStatus::Active,
-            ErrorCode::SwitchNotActive
-        );
+        require!(uri.len() <= MAX_CANARY_URI_LEN, ErrorCode::InvalidCanaryUri);
 
-        require!(
-            clock.unix_timestamp > switch.heartbeat_deadline,
-            ErrorCode::DeadlineNotPassed
+        switch.record_heartbeat(clock.unix_timestamp)?;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
         );
 
-        // Mark as expired first to prevent re-entrancy
-        switch.status = SwitchStatus::Expired;
+        let canary = switch.canary.as_mut().ok_or(ErrorCode::NotCanary)?;
+        canary.statement_hash = statement_hash;
+        canary.uri = uri.clone();
+        canary.published_at = clock.unix_timestamp;
 
-        msg!("Switch expired. Distributing funds to beneficiaries.");
+        emit!(CanaryPublished {
+            switch: switch.key(),
+            statement_hash,
+            uri,
+        });
+
+        msg!("Canary statement published. New deadline: {}", switch.heartbeat_deadline);
 
         Ok(())
     }
 
-    /// Distribute SOL from escrow to beneficiaries
-    pub fn distribute_sol(ctx: Context<DistributeSol>) -> Result<()> {
-        let switch = &ctx.accounts.switch;
+    /// Create the owner's switch registry, which hands out counter-based switch IDs
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+
+        registry.owner = ctx.accounts.owner.key();
+        registry.switch_count = 0;
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Switch registry initialized for {}", registry.owner);
+
+        Ok(())
+    }
+
+    /// Initialize a switch seeded by the registry's next index instead of a free-form ID.
+    /// Remaining accounts are the beneficiaries' index PDAs.
+    pub fn initialize_indexed_switch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeIndexedSwitch<'info>>,
+        label: String,
+        timeout_seconds: i64,
+        beneficiaries: Vec<Beneficiary>,
+        token_type: TokenType,
+    ) -> Result<()> {
+        require!(label.len() <= MAX_LABEL_LEN, ErrorCode::InvalidLabel);
 
         require!(
-            switch.status == SwitchStatus::Expired,
-            ErrorCode::SwitchNotExpired
+            !beneficiaries.is_empty() && beneficiaries.len() <= MAX_BENEFICIARIES,
+            ErrorCode::InvalidBeneficiaryCount
         );
 
+        // Validate shares sum to 10000 basis points (100%)
+        let total_shares: u16 = beneficiaries.iter().map(|b| b.share_bps).sum();
         require!(
-            matches!(switch.token_type, TokenType::Sol),
-            ErrorCode::InvalidTokenType
+            total_shares == BASIS_POINTS_TOTAL,
+            ErrorCode::InvalidShareDistribution
         );
 
-        let escrow_balance = ctx.accounts.escrow.lamports();
-        let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
-        let distributable = escrow_balance.saturating_sub(rent_exempt);
+        require!(timeout_seconds > 0, ErrorCode::InvalidTimeout);
 
-        require!(distributable > 0, ErrorCode::InsufficientFunds);
+        let registry = &mut ctx.accounts.registry;
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
 
-        // Find the beneficiary for this transaction
-        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
-        let beneficiary_info = switch
-            .beneficiaries
-            .iter()
-            .find(|b| b.address == beneficiary_pubkey)
-            .ok_or(ErrorCode::BeneficiaryNotFound)?;
+        switch.owner = ctx.accounts.owner.key();
+        switch.switch_id = registry.next_switch_id();
+        switch.label = label;
+        switch.beneficiaries = beneficiaries;
+        switch.token_type = token_type;
+        switch.timeout_seconds = timeout_seconds;
+        switch.heartbeat_deadline = clock.unix_timestamp + timeout_seconds;
+        switch.last_heartbeat_at = clock.unix_timestamp;
+        switch.status = SwitchStatus::Active;
+        switch.bump = ctx.bumps.switch;
 
-        let amount = (distributable as u128)
-            .checked_mul(beneficiary_info.share_bps as u128)
-            .unwrap()
-            .checked_div(BASIS_POINTS_TOTAL as u128)
-            .unwrap() as u64;
+        registry.switch_count += 1;
 
-        // Transfer from escrow PDA to beneficiary
-        let owner_key = switch.owner;
-        let switch_id_bytes = switch.switch_id.as_bytes();
-        
-        let seeds = &[
-            b"escrow",
-            owner_key.as_ref(),
-            switch_id_bytes,
-        ];
-        
-        // Find the bump from the PDA derivation
-        let (_, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
-        let seeds_with_bump = &[
-            b"escrow",
-            owner_key.as_ref(),
-            switch_id_bytes,
-            &[bump],
-        ];
-        let signer_seeds = &[&seeds_with_bump[..]];
+        let history = &mut ctx.accounts.history;
+        history.switch = switch.key();
+        history.bump = ctx.bumps.history;
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            SystemTransfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.beneficiary.to_account_info(),
-            },
-            signer_seeds,
+        add_to_beneficiary_indexes(
+            switch.key(),
+            &switch.beneficiaries,
+            ctx.remaining_accounts,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        msg!(
+            "Switch '{}' initialized. Deadline: {}",
+            switch.switch_id,
+            switch.heartbeat_deadline
         );
 
-        transfer(cpi_context, amount)?;
+        Ok(())
+    }
 
-        msg!(
-            "Distributed {} lamports to beneficiary {}",
-            amount,
-            beneficiary_pubkey
+    /// Send a heartbeat to extend the deadline
+    pub fn send_heartbeat(ctx: Context<SendHeartbeat>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.record_heartbeat(clock.unix_timestamp)?;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
         );
 
+        msg!("Heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+
         Ok(())
     }
 
-    /// Distribute specific asset amount to beneficiary (enhanced version)
-    pub fn distribute_asset(
-        ctx: Context<DistributeAsset>,
-        amount: u64,
-        asset_type: AssetType,
+    /// Register the hash of a duress code, or remove it. Needs guardians from
+    /// `set_cancel_timelock`, since only they can lift the duress state.
+    pub fn set_duress_code(
+        ctx: Context<SetDuressCode>,
+        _switch_id: String,
+        code_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        let switch = &ctx.accounts.switch;
+        let switch = &mut ctx.accounts.switch;
 
         require!(
-            switch.status == SwitchStatus::Expired,
-            ErrorCode::SwitchNotExpired
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
         );
 
-        // Verify beneficiary is in the list
-        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
         require!(
-            switch.beneficiaries.iter().any(|b| b.address == beneficiary_pubkey),
-            ErrorCode::BeneficiaryNotFound
+            code_hash.is_none() || !switch.guardians.is_empty(),
+            ErrorCode::DuressRequiresGuardians
         );
 
-        match asset_type {
-            AssetType::Sol => {
-                // Transfer SOL
-                let owner_key = switch.owner;
-                let seeds = &[
-                    b"escrow",
-                    owner_key.as_ref(),
-                    &[switch.bump],
-                ];
-                let signer_seeds = &[&seeds[..]];
+        switch.duress_code_hash = code_hash;
 
-                let cpi_context = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    SystemTransfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.beneficiary.to_account_info(),
-                    },
-                    signer_seeds,
-                );
+        msg!("Duress code {}", if code_hash.is_some() { "set" } else { "removed" });
 
-                transfer(cpi_context, amount)?;
-                msg!("Distributed {} lamports (SOL) to {}", amount, beneficiary_pubkey);
-            }
-            AssetType::SplToken { mint } => {
-                // Transfer SPL tokens
-                let owner_key = switch.owner;
-                let seeds = &[
-                    b"escrow",
-                    owner_key.as_ref(),
-                    &[switch.bump],
-                ];
-                let signer_seeds = &[&seeds[..]];
+        Ok(())
+    }
 
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.escrow_token_account.to_account_info(),
-                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow.to_account_info(),
-                };
+    /// Send a heartbeat that carries a code. Any code counts as a plain heartbeat, but the
+    /// duress code also moves the switch into the Duress state, freezing cancel and
+    /// withdrawals, and emits `DuressSignaled`. Both look the same to the submitter.
+    /// Hash-chained switches pass the next preimage, as for `send_chained_heartbeat`.
+    pub fn send_coded_heartbeat(
+        ctx: Context<SendHeartbeat>,
+        _switch_id: String,
+        code: String,
+        preimage: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
 
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        match preimage {
+            Some(preimage) => switch.record_chained_heartbeat(clock.unix_timestamp, preimage)?,
+            None => switch.record_heartbeat(clock.unix_timestamp)?,
+        }
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
+        );
 
-                token::transfer(cpi_ctx, amount)?;
-                msg!("Distributed {} tokens ({}) to {}", amount, mint, beneficiary_pubkey);
+        let switch_key = switch.key();
+        let code_hash = hash(&[switch_key.as_ref(), code.as_bytes()].concat()).to_bytes();
+        if switch.duress_code_hash == Some(code_hash) {
+            if switch.status != SwitchStatus::Duress {
+                switch.status = SwitchStatus::Duress;
+                switch.duress_at = clock.unix_timestamp;
+                switch.duress_clear_votes.clear();
             }
+
+            emit!(DuressSignaled {
+                switch: switch_key,
+                signaled_at: clock.unix_timestamp,
+            });
         }
 
+        msg!("Heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+
         Ok(())
     }
 
-    /// Distribute SPL tokens from escrow to beneficiaries
-    pub fn distribute_spl(ctx: Context<DistributeSpl>) -> Result<()> {
-        let switch = &ctx.accounts.switch;
+    /// Guardian vote to lift the duress state; a majority of guardians returns the
+    /// switch to Active
+    pub fn clear_duress(ctx: Context<ClearDuress>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
 
         require!(
-            switch.status == SwitchStatus::Expired,
-            ErrorCode::SwitchNotExpired
+            switch.status == SwitchStatus::Duress,
+            ErrorCode::SwitchNotInDuress
         );
 
+        let guardian = ctx.accounts.guardian.key();
         require!(
-            matches!(switch.token_type, TokenType::Spl { .. }),
-            ErrorCode::InvalidTokenType
+            switch.guardians.contains(&guardian),
+            ErrorCode::NotGuardian
         );
 
-        let escrow_balance = ctx.accounts.escrow_token_account.amount;
-        require!(escrow_balance > 0, ErrorCode::InsufficientFunds);
-
-        // Find the beneficiary for this transaction
-        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
-        let beneficiary_info = switch
-            .beneficiaries
-            .iter()
-            .find(|b| b.address == beneficiary_pubkey)
-            .ok_or(ErrorCode::BeneficiaryNotFound)?;
-
-        let amount = (escrow_balance as u128)
-            .checked_mul(beneficiary_info.share_bps as u128)
-            .unwrap()
-            .checked_div(BASIS_POINTS_TOTAL as u128)
-            .unwrap() as u64;
-
-        // Transfer from escrow token account to beneficiary token account
-        let owner_key = switch.owner;
-        let seeds = &[
-            b"escrow",
-            owner_key.as_ref(),
-            &[switch.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        require!(
+            !switch.duress_clear_votes.contains(&guardian),
+            ErrorCode::DuressClearAlreadyVoted
+        );
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.beneficiary_token_account.to_account_info(),
-            authority: ctx.accounts.escrow.to_account_info(),
-        };
+        switch.duress_clear_votes.push(guardian);
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        if switch.duress_clear_votes.len() > switch.guardians.len() / 2 {
+            switch.status = SwitchStatus::Active;
+            switch.duress_at = 0;
 
-        token::transfer(cpi_ctx, amount)?;
+            emit!(DuressCleared {
+                switch: switch.key(),
+                votes: std::mem::take(&mut switch.duress_clear_votes),
+            });
 
-        msg!(
-            "Distributed {} tokens to beneficiary {}",
-            amount,
-            beneficiary_pubkey
-        );
+            msg!("Duress cleared by guardians");
+        }
 
         Ok(())
     }
 
-    /// Cancel the switch and return funds to owner
-    pub fn cancel_switch(ctx: Context<CancelSwitch>, switch_id: String) -> Result<()> {
+    /// Register the owner's passkey (compressed secp256r1 public key), or remove it
+    pub fn set_passkey(
+        ctx: Context<SetPasskey>,
+        _switch_id: String,
+        passkey: Option<[u8; 33]>,
+    ) -> Result<()> {
         let switch = &mut ctx.accounts.switch;
 
         require!(
@@ -373,88 +489,4044 @@ pub mod deadmansswitch {
             ErrorCode::SwitchNotActive
         );
 
-        switch.status = SwitchStatus::Canceled;
+        switch.passkey = passkey;
 
-        msg!("Switch '{}' canceled by owner", switch_id);
+        msg!("Passkey {}", if passkey.is_some() { "registered" } else { "removed" });
 
         Ok(())
     }
 
-    /// Withdraw SOL after cancellation
-    pub fn withdraw_sol(ctx: Context<WithdrawSol>, switch_id: String) -> Result<()> {
-        let switch = &ctx.accounts.switch;
-
+    /// Heartbeat from a WebAuthn passkey assertion, submitted by anyone. The preceding
+    /// instruction must be the secp256r1 precompile verifying the passkey's signature over
+    /// `authenticator_data || sha256(client_data_json)`, and the client data must carry
+    /// the challenge `base64url(sha256(switch || passkey_nonce))`.
+    pub fn send_passkey_heartbeat(
+        ctx: Context<SendPasskeyHeartbeat>,
+        _switch_id: String,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        let passkey = switch.passkey.ok_or(ErrorCode::PasskeyNotSet)?;
+        let message = secp256r1_verified_message(&ctx.accounts.instructions, &passkey)?;
+
+        let signed = [
+            authenticator_data.as_slice(),
+            &hash(&client_data_json).to_bytes(),
+        ]
+        .concat();
+        require!(message == signed, ErrorCode::InvalidPasskeyAssertion);
+
         require!(
-            switch.status == SwitchStatus::Canceled,
-            ErrorCode::SwitchNotCanceled
+            authenticator_data.len() >= 37
+                && authenticator_data[32] & WEBAUTHN_USER_PRESENT != 0,
+            ErrorCode::InvalidPasskeyAssertion
         );
 
-        let escrow_balance = ctx.accounts.escrow.lamports();
-        let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
-        let withdrawable = escrow_balance.saturating_sub(rent_exempt);
+        // The nonce in the challenge makes every assertion single-use
+        let switch_key = switch.key();
+        let challenge = hash(&[switch_key.as_ref(), &switch.passkey_nonce.to_le_bytes()].concat());
+        let client_data = std::str::from_utf8(&client_data_json)
+            .map_err(|_| ErrorCode::InvalidPasskeyAssertion)?;
+        require!(
+            client_data.contains(r#""type":"webauthn.get""#)
+                && client_data.contains(&format!(
+                    r#""challenge":"{}""#,
+                    base64url_encode(&challenge.to_bytes())
+                )),
+            ErrorCode::InvalidPasskeyAssertion
+        );
+
+        switch.record_heartbeat(clock.unix_timestamp)?;
+        switch.passkey_nonce += 1;
+
+        // A passkey does not fit in a pubkey, so the history records its hash
+        ctx.accounts.history.append(
+            Pubkey::new_from_array(hash(&passkey).to_bytes()),
+            HeartbeatMethod::SignedMessage,
+            &clock,
+        );
+
+        msg!("Passkey heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Register an Ethereum address allowed to send heartbeats, or remove it
+    pub fn set_eth_address(
+        ctx: Context<SetEthAddress>,
+        _switch_id: String,
+        eth_address: Option<[u8; 20]>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        switch.eth_address = eth_address;
+
+        msg!("Ethereum address {}", if eth_address.is_some() { "registered" } else { "removed" });
+
+        Ok(())
+    }
+
+    /// Heartbeat from an Ethereum key, submitted by any fee payer. The preceding instruction
+    /// must be the secp256k1 precompile verifying the registered address's EIP-191
+    /// signature over `eth_heartbeat_message(switch, eth_nonce)`.
+    pub fn send_eth_heartbeat(ctx: Context<SendEthHeartbeat>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        let eth_address = switch.eth_address.ok_or(ErrorCode::EthAddressNotSet)?;
+        let message = secp256k1_verified_message(&ctx.accounts.instructions, &eth_address)?;
+
+        // The nonce in the message makes every signature single-use
+        let expected = eip191_message(&eth_heartbeat_message(&switch.key(), switch.eth_nonce));
+        require!(message == expected, ErrorCode::InvalidEthHeartbeatMessage);
+
+        switch.record_heartbeat(clock.unix_timestamp)?;
+        switch.eth_nonce += 1;
+
+        // The history records the address left-padded to 32 bytes, as in an EVM word
+        let mut signer = [0u8; 32];
+        signer[12..].copy_from_slice(&eth_address);
+        ctx.accounts.history.append(
+            Pubkey::new_from_array(signer),
+            HeartbeatMethod::SignedMessage,
+            &clock,
+        );
+
+        msg!("Ethereum heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Commit to the tip of a hash chain. From then on heartbeats must reveal the next
+    /// preimage through `send_chained_heartbeat`, so the owner key alone cannot keep the
+    /// switch alive. Set it in the same transaction as the switch is created.
+    pub fn set_heartbeat_chain(
+        ctx: Context<SetHeartbeatChain>,
+        _switch_id: String,
+        tip: [u8; 32],
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        // Re-seeding has to prove knowledge of the current chain
+        require!(
+            switch.heartbeat_chain.is_none(),
+            ErrorCode::HeartbeatChainAlreadySet
+        );
+
+        // Profile heartbeats carry no preimage
+        require!(switch.profile.is_none(), ErrorCode::SwitchLinkedToProfile);
+
+        // Nor do co-owner heartbeats, which keep a joint switch alive on their own
+        require!(switch.co_owners.is_empty(), ErrorCode::JointSwitchNotChainable);
+
+        switch.heartbeat_chain = Some(tip);
+
+        msg!("Heartbeat chain committed");
+
+        Ok(())
+    }
+
+    /// Send a heartbeat on a hash-chained switch by revealing the preimage of the
+    /// last committed value
+    pub fn send_chained_heartbeat(
+        ctx: Context<SendHeartbeat>,
+        _switch_id: String,
+        preimage: [u8; 32],
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.record_chained_heartbeat(clock.unix_timestamp, preimage)?;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
+        );
+
+        msg!("Chained heartbeat received. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Replace an exhausted or exposed hash chain. Revealing the next preimage counts as
+    /// a heartbeat; a `new_tip` of None turns the chain off.
+    pub fn reseed_heartbeat_chain(
+        ctx: Context<SendHeartbeat>,
+        _switch_id: String,
+        preimage: [u8; 32],
+        new_tip: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.record_chained_heartbeat(clock.unix_timestamp, preimage)?;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
+        );
+
+        switch.heartbeat_chain = new_tip;
+
+        msg!(
+            "Heartbeat chain {}. New deadline: {}",
+            if new_tip.is_some() { "re-seeded" } else { "removed" },
+            switch.heartbeat_deadline
+        );
+
+        Ok(())
+    }
+
+    /// Send a heartbeat to every owner switch passed in `remaining_accounts` as
+    /// (switch, heartbeat history) pairs.
+    /// Switches that cannot take a heartbeat are skipped; each result is emitted as an event.
+    pub fn send_heartbeat_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendHeartbeatBatch<'info>>,
+    ) -> Result<()> {
+        let owner_key = ctx.accounts.owner.key();
+        let clock = Clock::get()?;
+
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidSwitchAccount
+        );
+
+        let mut refreshed = 0;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let account = &pair[0];
+            require!(
+                account.is_writable && pair[1].is_writable,
+                ErrorCode::InvalidSwitchAccount
+            );
+
+            let mut switch = Account::<Switch>::try_from(account)?;
+            let mut history = Account::<HeartbeatHistory>::try_from(&pair[1])?;
+
+            require_keys_eq!(switch.owner, owner_key, ErrorCode::NotSwitchOwner);
+
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"switch",
+                    owner_key.as_ref(),
+                    switch.switch_id.as_bytes(),
+                    &[switch.bump],
+                ],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidSwitchAccount)?;
+            require_keys_eq!(expected, account.key(), ErrorCode::InvalidSwitchAccount);
+            require_keys_eq!(history.switch, account.key(), ErrorCode::InvalidHeartbeatHistory);
+
+            let success = switch.record_heartbeat(clock.unix_timestamp).is_ok();
+            if success {
+                history.append(owner_key, HeartbeatMethod::Direct, &clock);
+                switch.exit(ctx.program_id)?;
+                history.exit(ctx.program_id)?;
+                refreshed += 1;
+            }
+
+            emit!(BatchHeartbeatResult {
+                switch: account.key(),
+                switch_id: switch.switch_id.clone(),
+                success,
+                heartbeat_deadline: switch.heartbeat_deadline,
+            });
+        }
+
+        msg!(
+            "Batch heartbeat refreshed {} of {} switches",
+            refreshed,
+            ctx.remaining_accounts.len() / 2
+        );
+
+        Ok(())
+    }
+
+    /// Turn the switch into a joint switch with additional co-owners
+    pub fn set_joint_owners(
+        ctx: Context<SetJointOwners>,
+        _switch_id: String,
+        co_owners: Vec<Pubkey>,
+        expiry_policy: ExpiryPolicy,
+        owner_quorum: u8,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.co_owners.is_empty()
+                && !co_owners.is_empty()
+                && co_owners.len() <= MAX_CO_OWNERS,
+            ErrorCode::InvalidCoOwners
+        );
+
+        // Co-owners named with the owner key alone could keep a chained switch alive
+        require!(
+            switch.heartbeat_chain.is_none(),
+            ErrorCode::HeartbeatPreimageRequired
+        );
+
+        // Co-owners must be unique and distinct from the primary owner
+        for (i, co_owner) in co_owners.iter().enumerate() {
+            require!(
+                *co_owner != switch.owner && !co_owners[..i].contains(co_owner),
+                ErrorCode::InvalidCoOwners
+            );
+        }
+
+        // Total owners including the primary owner
+        let total_owners = co_owners.len() + 1;
+
+        if let ExpiryPolicy::Threshold { missed } = expiry_policy {
+            require!(
+                missed > 0 && missed as usize <= total_owners,
+                ErrorCode::InvalidExpiryPolicy
+            );
+        }
+
+        require!(
+            owner_quorum > 0 && owner_quorum as usize <= total_owners,
+            ErrorCode::InvalidOwnerQuorum
+        );
+
+        switch.co_owners = co_owners
+            .iter()
+            .map(|key| CoOwner {
+                key: *key,
+                last_heartbeat_at: clock.unix_timestamp,
+            })
+            .collect();
+        switch.expiry_policy = expiry_policy;
+        switch.owner_quorum = owner_quorum;
+
+        msg!(
+            "Joint switch configured with {} owners. Quorum: {}",
+            total_owners,
+            owner_quorum
+        );
+
+        Ok(())
+    }
+
+    /// Send a heartbeat as one of the co-owners of a joint switch
+    pub fn send_co_owner_heartbeat(
+        ctx: Context<SendCoOwnerHeartbeat>,
+        _switch_id: String,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(switch.is_live(), ErrorCode::SwitchNotActive);
+
+        require!(
+            clock.unix_timestamp <= switch.effective_deadline(ctx.accounts.profile.as_ref())?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        let min_interval = switch.min_heartbeat_interval_seconds;
+        let co_owner_key = ctx.accounts.co_owner.key();
+        let co_owner = switch
+            .co_owners
+            .iter_mut()
+            .find(|c| c.key == co_owner_key)
+            .ok_or(ErrorCode::NotCoOwner)?;
+
+        require!(
+            clock.unix_timestamp >= co_owner.last_heartbeat_at.saturating_add(min_interval),
+            ErrorCode::HeartbeatTooSoon
+        );
+
+        co_owner.last_heartbeat_at = clock.unix_timestamp;
+        ctx.accounts
+            .history
+            .append(co_owner_key, HeartbeatMethod::Direct, &clock);
+
+        msg!("Heartbeat received from co-owner {}", co_owner_key);
+
+        Ok(())
+    }
+
+    /// Create the owner's profile, whose single heartbeat keeps every linked switch alive
+    pub fn initialize_profile(ctx: Context<InitializeProfile>, timeout_seconds: i64) -> Result<()> {
+        require!(timeout_seconds > 0, ErrorCode::InvalidTimeout);
+
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        profile.owner = ctx.accounts.owner.key();
+        profile.timeout_seconds = timeout_seconds;
+        profile.heartbeat_deadline = clock.unix_timestamp + timeout_seconds;
+        profile.linked_switches = 0;
+        profile.bump = ctx.bumps.profile;
+
+        msg!("Profile initialized. Deadline: {}", profile.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Send a heartbeat on the profile, extending every linked switch.
+    /// Remaining accounts are the (switch, heartbeat history) pairs of every live switch
+    /// linked to the profile, each of which records the heartbeat.
+    pub fn send_profile_heartbeat<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendProfileHeartbeat<'info>>,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        let owner_key = ctx.accounts.owner.key();
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp <= profile.heartbeat_deadline,
+            ErrorCode::ProfileExpired
+        );
+
+        require!(
+            ctx.remaining_accounts.len() == profile.linked_switches as usize * 2,
+            ErrorCode::LinkedSwitchesRequired
+        );
+
+        let profile_key = profile.key();
+        let last_profile_heartbeat_at = profile.heartbeat_deadline - profile.timeout_seconds;
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(profile.linked_switches as usize);
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let account = &pair[0];
+            require!(
+                account.is_writable && pair[1].is_writable,
+                ErrorCode::InvalidSwitchAccount
+            );
+            require!(
+                !seen.contains(&account.key()),
+                ErrorCode::LinkedSwitchesRequired
+            );
+            seen.push(account.key());
+
+            let mut switch = Account::<Switch>::try_from(account)?;
+            let mut history = Account::<HeartbeatHistory>::try_from(&pair[1])?;
+
+            // Only the owner can link a switch, so the link vouches for it
+            require!(
+                switch.profile == Some(profile_key),
+                ErrorCode::ProfileMismatch
+            );
+            require_keys_eq!(history.switch, account.key(), ErrorCode::InvalidHeartbeatHistory);
+
+            switch.record_profile_heartbeat(clock.unix_timestamp, last_profile_heartbeat_at)?;
+            history.append(owner_key, HeartbeatMethod::Direct, &clock);
+            switch.exit(ctx.program_id)?;
+            history.exit(ctx.program_id)?;
+        }
+
+        profile.heartbeat_deadline = clock.unix_timestamp + profile.timeout_seconds;
+
+        msg!("Profile heartbeat received. New deadline: {}", profile.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Link a switch to the owner's profile so the profile deadline applies to it
+    pub fn link_profile(ctx: Context<LinkProfile>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(switch.profile.is_none(), ErrorCode::SwitchLinkedToProfile);
+
+        // Profile heartbeats carry no preimage
+        require!(
+            switch.heartbeat_chain.is_none(),
+            ErrorCode::HeartbeatPreimageRequired
+        );
+
+        // A canary must be verifiable from the switch account alone
+        require!(switch.canary.is_none(), ErrorCode::NotForCanary);
+
+        require!(
+            clock.unix_timestamp <= switch.effective_deadline(None)?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        require!(
+            clock.unix_timestamp <= profile.heartbeat_deadline,
+            ErrorCode::ProfileExpired
+        );
+
+        switch.profile = Some(profile.key());
+        profile.linked_switches += 1;
+
+        msg!("Switch linked to profile {}", profile.key());
+
+        Ok(())
+    }
+
+    /// Unlink a switch from the profile, restarting its own heartbeat timer
+    pub fn unlink_profile(ctx: Context<LinkProfile>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.profile == Some(profile.key()),
+            ErrorCode::ProfileMismatch
+        );
+
+        require!(
+            clock.unix_timestamp <= switch.effective_deadline(Some(profile))?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        switch.profile = None;
+        profile.linked_switches -= 1;
+        switch.heartbeat_deadline = switch.next_deadline(clock.unix_timestamp);
+
+        msg!("Switch unlinked from profile. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Set the fallback address that receives unclaimed funds after the claim deadline.
+    /// Shorthand for proposing a fallback change, so it is staged behind the config delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn set_fallback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetFallback<'info>>,
+        _switch_id: String,
+        fallback: Pubkey,
+        claim_deadline_seconds: i64,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        let change = ConfigChange::Fallback {
+            fallback,
+            claim_deadline_seconds,
+        };
+
+        if !stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            apply_config_change(
+                switch,
+                change,
+                ctx.remaining_accounts,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Set how long config changes wait before they can be applied. Once set, the delay
+    /// can only be lengthened.
+    pub fn set_config_delay(
+        ctx: Context<SetConfigDelay>,
+        _switch_id: String,
+        delay_seconds: i64,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(delay_seconds >= 0, ErrorCode::InvalidConfigDelay);
+
+        // Otherwise a coerced owner could drop the delay and redirect the estate at once
+        require!(
+            delay_seconds >= switch.config_delay_seconds,
+            ErrorCode::ConfigDelayLocked
+        );
+
+        switch.config_delay_seconds = delay_seconds;
+
+        msg!("Config change delay set to {} seconds", delay_seconds);
+
+        Ok(())
+    }
+
+    /// Propose a config change. The change is staged until the config delay has passed,
+    /// or applied at once when no delay is set.
+    /// Remaining accounts are co-owner signers when an owner quorum is set and, for an
+    /// immediate beneficiary change, the old and new beneficiaries' index PDAs.
+    pub fn propose_config_change<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProposeConfigChange<'info>>,
+        _switch_id: String,
+        change: ConfigChange,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        if !stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            apply_config_change(
+                switch,
+                change,
+                ctx.remaining_accounts,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop the pending config change before it is applied
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        let pending = switch
+            .pending_config_change
+            .take()
+            .ok_or(ErrorCode::NoPendingConfigChange)?;
+
+        emit!(ConfigChangeCanceled {
+            switch: switch.key(),
+            change: pending.change,
+        });
+
+        msg!("Pending config change canceled");
+
+        Ok(())
+    }
+
+    /// Apply the pending config change once its delay has passed. Anyone can crank it,
+    /// but only while the switch is active and not past its deadline, so an expiry
+    /// during the delay is settled under the old configuration.
+    /// Remaining accounts are the old and new beneficiaries' index PDAs for a beneficiary change.
+    pub fn apply_pending_config_change<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApplyConfigChange<'info>>,
+        _switch_id: String,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            clock.unix_timestamp <= switch.effective_deadline(ctx.accounts.profile.as_ref())?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        let pending = switch
+            .pending_config_change
+            .clone()
+            .ok_or(ErrorCode::NoPendingConfigChange)?;
+
+        require!(
+            clock.unix_timestamp >= pending.activates_at,
+            ErrorCode::ConfigChangeDelayActive
+        );
+
+        // The configuration may have moved on since the change was proposed
+        switch.validate_config_change(&pending.change)?;
+
+        apply_config_change(
+            switch,
+            pending.change,
+            ctx.remaining_accounts,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Set the window after expiry during which distribution is blocked and the owner can revive.
+    /// Shorthand for proposing a challenge period change, so it is staged behind the config delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn set_challenge_period<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetChallengePeriod<'info>>,
+        _switch_id: String,
+        challenge_period_seconds: i64,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        let change = ConfigChange::ChallengePeriod {
+            challenge_period_seconds,
+        };
+
+        if !stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            apply_config_change(
+                switch,
+                change,
+                ctx.remaining_accounts,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Switch between a sliding deadline and fixed calendar or aligned windows.
+    /// Shorthand for proposing a schedule change, so it is staged behind the config delay.
+    /// Applied at once, it counts as a heartbeat under the new schedule; a staged schedule
+    /// takes effect from the first heartbeat after it is applied.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn set_heartbeat_schedule<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetHeartbeatSchedule<'info>>,
+        _switch_id: String,
+        schedule: HeartbeatSchedule,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        // Rejects Duress too: heartbeats pass under duress, owner actions do not
+        let change = ConfigChange::HeartbeatSchedule { schedule };
+        if stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            return Ok(());
+        }
+
+        apply_config_change(
+            switch,
+            change,
+            ctx.remaining_accounts,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        switch.record_heartbeat(clock.unix_timestamp)?;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
+        );
+
+        msg!("Heartbeat schedule updated. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Set the minimum time between heartbeats, 0 to disable
+    pub fn set_min_heartbeat_interval(
+        ctx: Context<SetMinHeartbeatInterval>,
+        _switch_id: String,
+        min_interval_seconds: i64,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        // The owner must still be able to check in before a sliding deadline
+        require!(
+            min_interval_seconds >= 0 && min_interval_seconds < switch.timeout_seconds,
+            ErrorCode::InvalidHeartbeatInterval
+        );
+
+        switch.min_heartbeat_interval_seconds = min_interval_seconds;
+
+        msg!("Minimum heartbeat interval set to {} seconds", min_interval_seconds);
+
+        Ok(())
+    }
+
+    /// Beneficiary asks the owner for proof of life. Unless the owner sends a heartbeat
+    /// within LIVENESS_CHALLENGE_SECONDS, the switch becomes expirable early.
+    pub fn challenge_liveness(ctx: Context<ChallengeLiveness>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(switch.is_live(), ErrorCode::SwitchNotActive);
+
+        require!(
+            clock.unix_timestamp <= switch.effective_deadline(ctx.accounts.profile.as_ref())?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        let beneficiary_key = ctx.accounts.beneficiary.key();
+        require!(
+            switch.beneficiaries.iter().any(|b| b.address == beneficiary_key),
+            ErrorCode::BeneficiaryNotFound
+        );
+
+        require!(
+            switch.liveness_challenge_deadline(ctx.accounts.profile.as_ref())?.is_none(),
+            ErrorCode::LivenessChallengePending
+        );
+
+        match switch
+            .liveness_challenges
+            .iter_mut()
+            .find(|c| c.beneficiary == beneficiary_key)
+        {
+            Some(challenge) => {
+                require!(
+                    clock.unix_timestamp
+                        >= challenge
+                            .challenged_at
+                            .saturating_add(LIVENESS_CHALLENGE_COOLDOWN_SECONDS),
+                    ErrorCode::LivenessChallengeCooldown
+                );
+                challenge.challenged_at = clock.unix_timestamp;
+            }
+            None => switch.liveness_challenges.push(LivenessChallenge {
+                beneficiary: beneficiary_key,
+                challenged_at: clock.unix_timestamp,
+            }),
+        }
+
+        switch.liveness_challenged_at = clock.unix_timestamp;
+        let deadline = clock.unix_timestamp + LIVENESS_CHALLENGE_SECONDS;
+
+        emit!(LivenessChallenged {
+            switch: switch.key(),
+            beneficiary: beneficiary_key,
+            deadline,
+        });
+
+        msg!("Liveness challenged by {}. Owner must answer by {}", beneficiary_key, deadline);
+
+        Ok(())
+    }
+
+    /// Trigger expiry and distribute funds to beneficiaries
+    pub fn trigger_expiry(ctx: Context<TriggerExpiry>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(switch.is_live(), ErrorCode::SwitchNotActive);
+
+        require!(
+            clock.unix_timestamp > switch.effective_deadline(ctx.accounts.profile.as_ref())?,
+            ErrorCode::DeadlineNotPassed
+        );
+
+        // Mark as expired first to prevent re-entrancy
+        switch.status = SwitchStatus::Expired;
+        switch.expired_at = clock.unix_timestamp;
+
+        // Profile heartbeats only cover live switches
+        if switch.profile.is_some() {
+            if let Some(profile) = ctx.accounts.profile.as_mut() {
+                profile.linked_switches -= 1;
+            }
+        }
+
+        let switch_key = switch.key();
+        if let Some(canary) = switch.canary.as_mut() {
+            canary.lapsed_at = clock.unix_timestamp;
+
+            emit!(CanaryLapsed {
+                switch: switch_key,
+                statement_hash: canary.statement_hash,
+                lapsed_at: clock.unix_timestamp,
+            });
+
+            msg!("Canary lapsed.");
+            return Ok(());
+        }
+
+        msg!("Switch expired. Distributing funds to beneficiaries.");
+
+        Ok(())
+    }
+
+    /// Report the switch's effective status through return data, for CPI callers and simulation
+    pub fn get_status(ctx: Context<GetStatus>) -> Result<SwitchStatusView> {
+        let switch = &ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        let deadline = switch.effective_deadline(ctx.accounts.profile.as_ref())?;
+
+        // A passed deadline counts as expired even if nobody has triggered it yet
+        let status = if switch.is_live() && clock.unix_timestamp > deadline {
+            SwitchStatus::Expired
+        } else {
+            switch.status.clone()
+        };
+
+        // Balance each beneficiary's share is computed from, unknown for an SPL
+        // switch read without its token account
+        let distributable = match &switch.token_type {
+            TokenType::Sol => {
+                let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
+                Some(ctx.accounts.escrow.lamports().saturating_sub(rent_exempt))
+            }
+            TokenType::Spl { mint } => match &ctx.accounts.escrow_token_account {
+                Some(token_account) => {
+                    require!(
+                        token_account.owner == ctx.accounts.escrow.key()
+                            && token_account.mint == *mint,
+                        ErrorCode::InvalidEscrowTokenAccount
+                    );
+                    Some(token_account.amount)
+                }
+                None => None,
+            },
+        };
+
+        let entitlements = switch
+            .beneficiaries
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let share_bps = switch.share_bps(i);
+                BeneficiaryEntitlement {
+                    address: b.address,
+                    share_bps,
+                    amount: distributable.map(|distributable| {
+                        (distributable as u128 * share_bps as u128
+                            / BASIS_POINTS_TOTAL as u128) as u64
+                    }),
+                }
+            })
+            .collect();
+
+        Ok(SwitchStatusView {
+            status,
+            heartbeat_deadline: deadline,
+            seconds_remaining: deadline.saturating_sub(clock.unix_timestamp).max(0),
+            token_type: switch.token_type.clone(),
+            entitlements,
+            last_heartbeat_at: switch.last_heartbeat_at,
+            heartbeat_count: switch.heartbeat_count,
+        })
+    }
+
+    /// Revive an expired switch during its challenge period. Hash-chained switches pass the
+    /// next preimage, as for `send_chained_heartbeat`.
+    pub fn revive_switch(
+        ctx: Context<ReviveSwitch>,
+        _switch_id: String,
+        preimage: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp <= switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodEnded
+        );
+
+        // Otherwise a stolen key alone could undo the expiry of a chained switch
+        match preimage {
+            Some(preimage) => switch.reveal_chain_preimage(preimage)?,
+            None => require!(
+                switch.heartbeat_chain.is_none(),
+                ErrorCode::HeartbeatPreimageRequired
+            ),
+        }
+
+        // A lapsed canary has been seen lapsing and stays that way
+        require!(switch.canary.is_none(), ErrorCode::CanaryLapsed);
+
+        // Nothing can leave the escrow during the challenge period, so the
+        // switch can go straight back to Active with a fresh deadline.
+        // Expiry does not lift duress, only guardians can.
+        switch.status = if switch.duress_at > 0 {
+            SwitchStatus::Duress
+        } else {
+            SwitchStatus::Active
+        };
+        switch.expired_at = 0;
+        switch.heartbeat_deadline = switch.next_deadline(clock.unix_timestamp);
+        switch.last_heartbeat_at = clock.unix_timestamp;
+        switch.heartbeat_count += 1;
+        ctx.accounts.history.append(
+            ctx.accounts.owner.key(),
+            HeartbeatMethod::Direct,
+            &clock,
+        );
+        for co_owner in switch.co_owners.iter_mut() {
+            co_owner.last_heartbeat_at = clock.unix_timestamp;
+        }
+
+        // Reviving proves the owner is alive, so a linked profile is refreshed too
+        if let Some(linked) = switch.profile {
+            let profile = ctx
+                .accounts
+                .profile
+                .as_mut()
+                .ok_or(ErrorCode::ProfileRequired)?;
+            require_keys_eq!(profile.key(), linked, ErrorCode::ProfileMismatch);
+            profile.heartbeat_deadline = clock.unix_timestamp + profile.timeout_seconds;
+            profile.linked_switches += 1;
+        }
+
+        msg!("Switch revived by owner. New deadline: {}", switch.heartbeat_deadline);
+
+        Ok(())
+    }
+
+    /// Appoint an executor who may adjust shares within `max_share_adjustment_bps`, pay the
+    /// approved expenses and order distributions during a window after the challenge period.
+    /// Passing no executor removes the role. Shorthand for proposing an executor change,
+    /// so it is staged behind the config delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn set_executor<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetExecutor<'info>>,
+        _switch_id: String,
+        executor: Option<Pubkey>,
+        max_share_adjustment_bps: u16,
+        executor_window_seconds: i64,
+        expenses: Vec<ApprovedExpense>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        let change = ConfigChange::Executor {
+            executor,
+            max_share_adjustment_bps,
+            executor_window_seconds,
+            expenses,
+        };
+
+        if !stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            apply_config_change(
+                switch,
+                change,
+                ctx.remaining_accounts,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Executor shifts shares between beneficiaries, each by at most the owner-set bound
+    pub fn executor_adjust_shares(
+        ctx: Context<ExecutorAction>,
+        share_adjustments: Vec<i16>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.require_executor_window(clock.unix_timestamp)?;
+
+        // Asset-allocated switches carry no shares to adjust
+        let total_shares: u16 = switch.beneficiaries.iter().map(|b| b.share_bps).sum();
+        require!(
+            total_shares == BASIS_POINTS_TOTAL,
+            ErrorCode::InvalidShareAdjustment
+        );
+
+        require!(
+            share_adjustments.len() == switch.beneficiaries.len()
+                && share_adjustments.iter().map(|a| *a as i32).sum::<i32>() == 0,
+            ErrorCode::InvalidShareAdjustment
+        );
+
+        for (beneficiary, adjustment) in switch.beneficiaries.iter().zip(share_adjustments.iter()) {
+            require!(
+                adjustment.unsigned_abs() <= switch.max_share_adjustment_bps
+                    && beneficiary.share_bps as i32 + *adjustment as i32 >= 0,
+                ErrorCode::InvalidShareAdjustment
+            );
+        }
+
+        switch.share_adjustments = share_adjustments;
+
+        msg!("Executor adjusted beneficiary shares");
+
+        Ok(())
+    }
+
+    /// Executor pays an owner-approved expense from the escrow, up to its cap
+    pub fn executor_pay_expense(ctx: Context<ExecutorPayExpense>, amount: u64) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.require_executor_window(clock.unix_timestamp)?;
+
+        let payee_key = ctx.accounts.payee.key();
+        let expense = switch
+            .approved_expenses
+            .iter_mut()
+            .find(|e| e.payee == payee_key)
+            .ok_or(ErrorCode::ExpenseNotApproved)?;
+
+        let paid = expense
+            .paid_lamports
+            .checked_add(amount)
+            .ok_or(ErrorCode::ExpenseCapExceeded)?;
+        require!(
+            amount > 0 && paid <= expense.cap_lamports,
+            ErrorCode::ExpenseCapExceeded
+        );
+        expense.paid_lamports = paid;
+
+        let escrow_balance = ctx.accounts.escrow.lamports();
+        let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
+        require!(
+            escrow_balance.saturating_sub(rent_exempt) >= amount,
+            ErrorCode::InsufficientFunds
+        );
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.payee.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer(cpi_context, amount)?;
+
+        msg!("Executor paid {} lamports to expense payee {}", amount, payee_key);
+
+        Ok(())
+    }
+
+    /// Executor fixes the order in which beneficiaries receive distributions, as beneficiary indices
+    pub fn executor_set_distribution_order(
+        ctx: Context<ExecutorAction>,
+        order: Vec<u8>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.require_executor_window(clock.unix_timestamp)?;
+
+        // Must name every beneficiary exactly once
+        let count = switch.beneficiaries.len();
+        require!(
+            order.len() == count
+                && (0..count).all(|i| order.iter().filter(|o| **o as usize == i).count() == 1),
+            ErrorCode::InvalidDistributionOrder
+        );
+
+        switch.distribution_order = order;
+
+        msg!("Executor set the distribution order");
+
+        Ok(())
+    }
+
+    /// Executor closes the window early, opening distribution
+    pub fn executor_finalize(ctx: Context<ExecutorAction>) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        switch.require_executor_window(clock.unix_timestamp)?;
+
+        switch.executor_finalized = true;
+
+        msg!("Executor finalized the estate, distribution is open");
+
+        Ok(())
+    }
+
+    /// Distribute SOL from escrow to beneficiaries
+    pub fn distribute_sol(ctx: Context<DistributeSol>) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        require!(
+            !switch.executor_window_open(clock.unix_timestamp),
+            ErrorCode::ExecutorWindowOpen
+        );
+
+        require!(
+            matches!(switch.token_type, TokenType::Sol),
+            ErrorCode::InvalidTokenType
+        );
+
+        let escrow_balance = ctx.accounts.escrow.lamports();
+        let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
+        let distributable = escrow_balance.saturating_sub(rent_exempt);
+
+        require!(distributable > 0, ErrorCode::InsufficientFunds);
+
+        // Find the beneficiary for this transaction
+        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
+        let beneficiary_index = switch
+            .beneficiaries
+            .iter()
+            .position(|b| b.address == beneficiary_pubkey)
+            .ok_or(ErrorCode::BeneficiaryNotFound)?;
+
+        switch.take_distribution_turn(beneficiary_index, false)?;
+
+        let amount = (distributable as u128)
+            .checked_mul(switch.share_bps(beneficiary_index) as u128)
+            .unwrap()
+            .checked_div(BASIS_POINTS_TOTAL as u128)
+            .unwrap() as u64;
+
+        // Transfer from escrow PDA to beneficiary
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+        ];
+        
+        // Find the bump from the PDA derivation
+        let (_, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
+        let seeds_with_bump = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds_with_bump[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.beneficiary.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer(cpi_context, amount)?;
+
+        msg!(
+            "Distributed {} lamports to beneficiary {}",
+            amount,
+            beneficiary_pubkey
+        );
+
+        Ok(())
+    }
+
+    /// Distribute specific asset amount to beneficiary (enhanced version)
+    pub fn distribute_asset(
+        ctx: Context<DistributeAsset>,
+        amount: u64,
+        asset_type: AssetType,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        require!(
+            !switch.executor_window_open(clock.unix_timestamp),
+            ErrorCode::ExecutorWindowOpen
+        );
+
+        // Verify beneficiary is in the list
+        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
+        let beneficiary_index = switch
+            .beneficiaries
+            .iter()
+            .position(|b| b.address == beneficiary_pubkey)
+            .ok_or(ErrorCode::BeneficiaryNotFound)?;
+
+        // A beneficiary may take several assets in a row before the next one's turn
+        switch.take_distribution_turn(beneficiary_index, true)?;
+
+        match asset_type {
+            AssetType::Sol => {
+                // Transfer SOL
+                let owner_key = switch.owner;
+                let seeds = &[
+                    b"escrow",
+                    owner_key.as_ref(),
+                    &[switch.bump],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let cpi_context = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    SystemTransfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.beneficiary.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+
+                transfer(cpi_context, amount)?;
+                msg!("Distributed {} lamports (SOL) to {}", amount, beneficiary_pubkey);
+            }
+            AssetType::SplToken { mint } => {
+                // Transfer SPL tokens
+                let owner_key = switch.owner;
+                let seeds = &[
+                    b"escrow",
+                    owner_key.as_ref(),
+                    &[switch.bump],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                };
+
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+                token::transfer(cpi_ctx, amount)?;
+                msg!("Distributed {} tokens ({}) to {}", amount, mint, beneficiary_pubkey);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Distribute SPL tokens from escrow to beneficiaries
+    pub fn distribute_spl(ctx: Context<DistributeSpl>) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        require!(
+            matches!(switch.token_type, TokenType::Spl { .. }),
+            ErrorCode::InvalidTokenType
+        );
+
+        require!(
+            !switch.executor_window_open(clock.unix_timestamp),
+            ErrorCode::ExecutorWindowOpen
+        );
+
+        let escrow_balance = ctx.accounts.escrow_token_account.amount;
+        require!(escrow_balance > 0, ErrorCode::InsufficientFunds);
+
+        // Find the beneficiary for this transaction
+        let beneficiary_pubkey = ctx.accounts.beneficiary.key();
+        let beneficiary_index = switch
+            .beneficiaries
+            .iter()
+            .position(|b| b.address == beneficiary_pubkey)
+            .ok_or(ErrorCode::BeneficiaryNotFound)?;
+
+        switch.take_distribution_turn(beneficiary_index, false)?;
+
+        let amount = (escrow_balance as u128)
+            .checked_mul(switch.share_bps(beneficiary_index) as u128)
+            .unwrap()
+            .checked_div(BASIS_POINTS_TOTAL as u128)
+            .unwrap() as u64;
+
+        // Transfer from escrow token account to beneficiary token account
+        let owner_key = switch.owner;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            &[switch.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!(
+            "Distributed {} tokens to beneficiary {}",
+            amount,
+            beneficiary_pubkey
+        );
+
+        Ok(())
+    }
+
+    /// Sweep everything left in escrow to the fallback once the claim deadline has passed.
+    /// Remaining accounts are (escrow token account, fallback token account) pairs, one
+    /// of which must be for the switch's mint on an SPL switch, plus the beneficiaries'
+    /// index PDAs. Stored expiry actions are closed along with the switch.
+    pub fn sweep_unclaimed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepUnclaimed<'info>>,
+    ) -> Result<()> {
+        let switch = &ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            switch.fallback.is_some(),
+            ErrorCode::FallbackNotSet
+        );
+
+        require!(
+            !switch.executor_window_open(clock.unix_timestamp),
+            ErrorCode::ExecutorWindowOpen
+        );
+
+        // Stake accounts and authorities go to their beneficiaries through
+        // `release_stake` and `release_authority`
+        require!(
+            switch.stake_accounts.is_empty(),
+            ErrorCode::StakeAccountsRegistered
+        );
+        require!(
+            switch.held_authorities.is_empty(),
+            ErrorCode::AuthoritiesHeld
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        let claim_deadline = switch
+            .expired_at
+            .checked_add(switch.claim_deadline_seconds)
+            .ok_or(ErrorCode::InvalidClaimDeadline)?;
+        require!(
+            clock.unix_timestamp > claim_deadline,
+            ErrorCode::ClaimDeadlineNotPassed
+        );
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let escrow_key = ctx.accounts.escrow.key();
+        let fallback_key = ctx.accounts.fallback.key();
+
+        // Index PDAs are owned by this program, token accounts by the token program
+        let token_accounts: Vec<_> = ctx
+            .remaining_accounts
+            .iter()
+            .filter(|account| account.owner != &crate::ID)
+            .collect();
+
+        require!(
+            token_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidEscrowTokenAccount
+        );
+
+        // Closing the switch without them would strand the balance it was set up for
+        let mut mint_swept = !matches!(switch.token_type, TokenType::Spl { .. });
+
+        // Drain and close every escrow token account into the fallback's token account
+        for pair in token_accounts.chunks(2) {
+            let escrow_token_account = Account::<TokenAccount>::try_from(pair[0])?;
+            let fallback_token_account = Account::<TokenAccount>::try_from(pair[1])?;
+
+            require!(
+                escrow_token_account.owner == escrow_key
+                    && fallback_token_account.owner == fallback_key
+                    && escrow_token_account.mint == fallback_token_account.mint,
+                ErrorCode::InvalidEscrowTokenAccount
+            );
+
+            let amount = escrow_token_account.amount;
+            if amount > 0 {
+                let cpi_accounts = Transfer {
+                    from: pair[0].clone(),
+                    to: pair[1].clone(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, amount)?;
+            }
+
+            let cpi_accounts = CloseAccount {
+                account: pair[0].clone(),
+                destination: ctx.accounts.fallback.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::close_account(cpi_ctx)?;
+
+            if switch.token_type == (TokenType::Spl { mint: escrow_token_account.mint }) {
+                mint_swept = true;
+            }
+
+            msg!(
+                "Swept {} tokens ({}) to fallback {}",
+                amount,
+                escrow_token_account.mint,
+                fallback_key
+            );
+        }
+
+        require!(mint_swept, ErrorCode::SweepTokenAccountRequired);
+
+        // Nothing is left to run the actions with, and the switch cannot clear them once closed
+        let expiry_actions = ctx.accounts.expiry_actions.to_account_info();
+        if expiry_actions.owner == &crate::ID {
+            let owner = ctx.accounts.owner.to_account_info();
+            **owner.try_borrow_mut_lamports()? += expiry_actions.lamports();
+            **expiry_actions.try_borrow_mut_lamports()? = 0;
+            expiry_actions.assign(&System::id());
+            expiry_actions.resize(0)?;
+        }
+
+        remove_from_beneficiary_indexes(
+            switch.key(),
+            &switch.beneficiaries,
+            ctx.remaining_accounts,
+        )?;
+
+        // Move every lamport out of the escrow, which closes it
+        let escrow_balance = ctx.accounts.escrow.lamports();
+        if escrow_balance > 0 {
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                SystemTransfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.fallback.to_account_info(),
+                },
+                signer_seeds,
+            );
+
+            transfer(cpi_context, escrow_balance)?;
+        }
+
+        msg!(
+            "Swept {} lamports to fallback {}. Switch closed.",
+            escrow_balance,
+            fallback_key
+        );
+
+        Ok(())
+    }
+
+    /// Hand a stake account's staker and withdrawer authorities to the escrow,
+    /// to be passed on to `beneficiary` on expiry
+    pub fn deposit_stake(
+        ctx: Context<DepositStake>,
+        _switch_id: String,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.beneficiaries.iter().any(|b| b.address == beneficiary),
+            ErrorCode::BeneficiaryNotFound
+        );
+
+        let stake_key = ctx.accounts.stake_account.key();
+        require!(
+            !switch.stake_accounts.iter().any(|s| s.stake_account == stake_key),
+            ErrorCode::StakeAccountAlreadyRegistered
+        );
+        require!(
+            switch.stake_accounts.len() < MAX_STAKE_ACCOUNTS,
+            ErrorCode::TooManyStakeAccounts
+        );
+
+        // Owner signs the transaction, so its authority carries through the CPI
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.stake_program.to_account_info(),
+                Authorize {
+                    stake: ctx.accounts.stake_account.to_account_info(),
+                    authorized: ctx.accounts.owner.to_account_info(),
+                    new_authorized: ctx.accounts.escrow.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                },
+            );
+            stake::authorize(cpi_ctx, stake_authorize, None)?;
+        }
+
+        switch.stake_accounts.push(StakeInheritance {
+            stake_account: stake_key,
+            beneficiary,
+        });
+
+        msg!("Stake account {} deposited for {}", stake_key, beneficiary);
+
+        Ok(())
+    }
+
+    /// Pass a registered stake account's authorities to its beneficiary after expiry
+    pub fn release_stake(ctx: Context<ReleaseStake>) -> Result<()> {
+        let clock = Clock::get()?;
+        let switch = &ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        let stake_key = ctx.accounts.stake_account.key();
+        let index = switch
+            .stake_accounts
+            .iter()
+            .position(|s| s.stake_account == stake_key)
+            .ok_or(ErrorCode::StakeAccountNotFound)?;
+
+        require!(
+            switch.stake_accounts[index].beneficiary == ctx.accounts.beneficiary.key(),
+            ErrorCode::BeneficiaryNotFound
+        );
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.stake_program.to_account_info(),
+                Authorize {
+                    stake: ctx.accounts.stake_account.to_account_info(),
+                    authorized: ctx.accounts.escrow.to_account_info(),
+                    new_authorized: ctx.accounts.beneficiary.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                },
+                signer_seeds,
+            );
+            stake::authorize(cpi_ctx, stake_authorize, None)?;
+        }
+
+        ctx.accounts.switch.stake_accounts.remove(index);
+
+        msg!(
+            "Stake account {} released to beneficiary {}",
+            stake_key,
+            ctx.accounts.beneficiary.key()
+        );
+
+        Ok(())
+    }
+
+    /// Return a registered stake account's authorities to the owner. With a cancel
+    /// timelock, needs a cancel request that has waited out the delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn reclaim_stake(ctx: Context<ReclaimStake>, switch_id: String) -> Result<()> {
+        let switch = &ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        switch.require_cancel_unlocked(Clock::get()?.unix_timestamp)?;
+
+        let stake_key = ctx.accounts.stake_account.key();
+        let index = switch
+            .stake_accounts
+            .iter()
+            .position(|s| s.stake_account == stake_key)
+            .ok_or(ErrorCode::StakeAccountNotFound)?;
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.stake_program.to_account_info(),
+                Authorize {
+                    stake: ctx.accounts.stake_account.to_account_info(),
+                    authorized: ctx.accounts.escrow.to_account_info(),
+                    new_authorized: ctx.accounts.owner.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                },
+                signer_seeds,
+            );
+            stake::authorize(cpi_ctx, stake_authorize, None)?;
+        }
+
+        ctx.accounts.switch.stake_accounts.remove(index);
+
+        msg!("Stake account {} returned to owner", stake_key);
+
+        Ok(())
+    }
+
+    /// Hand a program upgrade, mint or freeze authority to the escrow,
+    /// to be passed on to `beneficiary` on expiry
+    pub fn deposit_authority(
+        ctx: Context<DepositAuthority>,
+        _switch_id: String,
+        kind: AuthorityKind,
+        target: Pubkey,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.beneficiaries.iter().any(|b| b.address == beneficiary),
+            ErrorCode::BeneficiaryNotFound
+        );
+
+        let held = HeldAuthority {
+            kind,
+            target,
+            beneficiary,
+        };
+
+        require!(
+            held.target_account() == ctx.accounts.target_account.key(),
+            ErrorCode::InvalidAuthorityTarget
+        );
+        require!(
+            !switch
+                .held_authorities
+                .iter()
+                .any(|a| a.kind == held.kind && a.target == held.target),
+            ErrorCode::AuthorityAlreadyHeld
+        );
+        require!(
+            switch.held_authorities.len() < MAX_HELD_AUTHORITIES,
+            ErrorCode::TooManyAuthorities
+        );
+
+        // Owner signs the transaction, so its authority carries through the CPI
+        set_held_authority(
+            &held,
+            &ctx.accounts.target_account,
+            &ctx.accounts.owner,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            &ctx.accounts.bpf_loader_upgradeable,
+            &[],
+        )?;
+
+        msg!("{:?} authority over {} deposited for {}", held.kind, target, beneficiary);
+
+        switch.held_authorities.push(held);
+
+        Ok(())
+    }
+
+    /// Pass a held authority to its beneficiary after expiry
+    pub fn release_authority(ctx: Context<ReleaseAuthority>, kind: AuthorityKind) -> Result<()> {
+        let clock = Clock::get()?;
+        let switch = &ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        let target_key = ctx.accounts.target_account.key();
+        let index = switch
+            .held_authorities
+            .iter()
+            .position(|a| a.kind == kind && a.target_account() == target_key)
+            .ok_or(ErrorCode::AuthorityNotFound)?;
+
+        require!(
+            switch.held_authorities[index].beneficiary == ctx.accounts.beneficiary.key(),
+            ErrorCode::BeneficiaryNotFound
+        );
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        set_held_authority(
+            &switch.held_authorities[index],
+            &ctx.accounts.target_account,
+            &ctx.accounts.escrow,
+            &ctx.accounts.beneficiary,
+            &ctx.accounts.token_program,
+            &ctx.accounts.bpf_loader_upgradeable,
+            signer_seeds,
+        )?;
+
+        let held = ctx.accounts.switch.held_authorities.remove(index);
+
+        msg!(
+            "{:?} authority over {} released to beneficiary {}",
+            held.kind,
+            held.target,
+            held.beneficiary
+        );
+
+        Ok(())
+    }
+
+    /// Return a held authority to the owner. With a cancel timelock, needs a cancel
+    /// request that has waited out the delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn reclaim_authority(
+        ctx: Context<ReclaimAuthority>,
+        switch_id: String,
+        kind: AuthorityKind,
+    ) -> Result<()> {
+        let switch = &ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        switch.require_cancel_unlocked(Clock::get()?.unix_timestamp)?;
+
+        let target_key = ctx.accounts.target_account.key();
+        let index = switch
+            .held_authorities
+            .iter()
+            .position(|a| a.kind == kind && a.target_account() == target_key)
+            .ok_or(ErrorCode::AuthorityNotFound)?;
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        set_held_authority(
+            &switch.held_authorities[index],
+            &ctx.accounts.target_account,
+            &ctx.accounts.escrow,
+            &ctx.accounts.owner,
+            &ctx.accounts.token_program,
+            &ctx.accounts.bpf_loader_upgradeable,
+            signer_seeds,
+        )?;
+
+        let held = ctx.accounts.switch.held_authorities.remove(index);
+
+        msg!("{:?} authority over {} returned to owner", held.kind, held.target);
+
+        Ok(())
+    }
+
+    /// Store instructions to run with the escrow as signer once the switch expires.
+    /// With a config delay or cancel timelock, the actions' hash must first be approved
+    /// through a staged `ConfigChange::ExpiryActions`; storing consumes the approval.
+    pub fn store_expiry_actions(
+        ctx: Context<StoreExpiryActions>,
+        _switch_id: String,
+        actions: Vec<ExpiryAction>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        if switch.config_delay() > 0 {
+            let actions_hash = hash(&borsh::to_vec(&actions)?).to_bytes();
+            require!(
+                switch.expiry_actions_hash == Some(actions_hash),
+                ErrorCode::ExpiryActionsNotApproved
+            );
+            switch.expiry_actions_hash = None;
+        }
+
+        require!(
+            !actions.is_empty() && actions.len() <= MAX_EXPIRY_ACTIONS,
+            ErrorCode::InvalidExpiryAction
+        );
+
+        let escrow_key = ctx.accounts.escrow.key();
+        for action in actions.iter() {
+            require!(
+                action.program_id != crate::ID
+                    && action.accounts.len() <= MAX_ACTION_ACCOUNTS
+                    && action.data.len() <= MAX_ACTION_DATA_LEN
+                    && !action.executed,
+                ErrorCode::InvalidExpiryAction
+            );
+
+            // The escrow is the only signer the program can provide
+            require!(
+                action
+                    .accounts
+                    .iter()
+                    .all(|meta| !meta.is_signer || meta.pubkey == escrow_key),
+                ErrorCode::InvalidActionSigner
+            );
+        }
+
+        let expiry_actions = &mut ctx.accounts.expiry_actions;
+        expiry_actions.switch = switch.key();
+        expiry_actions.actions = actions;
+        expiry_actions.bump = ctx.bumps.expiry_actions;
+
+        msg!("Stored {} expiry actions", expiry_actions.actions.len());
+
+        Ok(())
+    }
+
+    /// Remove the stored expiry actions and reclaim their rent
+    pub fn clear_expiry_actions(ctx: Context<ClearExpiryActions>, _switch_id: String) -> Result<()> {
+        require!(
+            ctx.accounts.switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        msg!("Expiry actions cleared");
+
+        Ok(())
+    }
+
+    /// Run one stored expiry action with the escrow as signer.
+    /// Remaining accounts must include every account the action references and its program.
+    pub fn execute_expiry_action<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteExpiryAction<'info>>,
+        index: u8,
+    ) -> Result<()> {
+        let switch = &ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+
+        require!(
+            clock.unix_timestamp > switch.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        let action = ctx
+            .accounts
+            .expiry_actions
+            .actions
+            .get_mut(index as usize)
+            .ok_or(ErrorCode::InvalidExpiryAction)?;
+
+        require!(!action.executed, ErrorCode::ExpiryActionAlreadyExecuted);
+
+        // Mark executed before invoking so the action can never be replayed
+        action.executed = true;
+
+        let ix = Instruction {
+            program_id: action.program_id,
+            accounts: action
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: action.data.clone(),
+        };
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch.switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut account_infos = vec![ctx.accounts.escrow.to_account_info()];
+        account_infos.extend_from_slice(ctx.remaining_accounts);
+
+        invoke_signed(&ix, &account_infos, signer_seeds)?;
+
+        msg!("Executed expiry action {} on program {}", index, ix.program_id);
+
+        Ok(())
+    }
+
+    /// Require cancellations to be requested `delay_seconds` in advance, giving guardians
+    /// and beneficiaries time to freeze a cancel made with a stolen key. Once set, only
+    /// the delay can be lengthened. Config changes wait out the delay as well.
+    pub fn set_cancel_timelock(
+        ctx: Context<SetCancelTimelock>,
+        _switch_id: String,
+        delay_seconds: i64,
+        guardians: Vec<Pubkey>,
+        freeze_quorum: u8,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.cancel_requested_at == 0,
+            ErrorCode::CancelAlreadyRequested
+        );
+
+        // A change staged under a shorter delay would slip past the timelock
+        require!(
+            switch.pending_config_change.is_none(),
+            ErrorCode::ConfigChangePending
+        );
+
+        // A thief holding the owner key must not be able to weaken the timelock
+        if switch.cancel_delay_seconds > 0 {
+            require!(
+                delay_seconds >= switch.cancel_delay_seconds
+                    && guardians == switch.guardians
+                    && freeze_quorum == switch.freeze_quorum,
+                ErrorCode::CancelTimelockLocked
+            );
+        }
+
+        require!(
+            delay_seconds > 0 && guardians.len() <= MAX_GUARDIANS,
+            ErrorCode::InvalidCancelTimelock
+        );
+
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                *guardian != switch.owner && !guardians[..i].contains(guardian),
+                ErrorCode::InvalidCancelTimelock
+            );
+        }
+
+        require!(
+            freeze_quorum > 0
+                && freeze_quorum as usize <= guardians.len() + switch.beneficiaries.len(),
+            ErrorCode::InvalidCancelTimelock
+        );
+
+        switch.cancel_delay_seconds = delay_seconds;
+        switch.guardians = guardians;
+        switch.freeze_quorum = freeze_quorum;
+
+        msg!("Cancel timelock set to {} seconds", delay_seconds);
+
+        Ok(())
+    }
+
+    /// Start the cancel timelock. `cancel_switch` becomes possible once the delay has passed
+    /// unless guardians and beneficiaries freeze the request first.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn request_cancel(ctx: Context<RequestCancel>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.cancel_delay_seconds > 0,
+            ErrorCode::InvalidCancelTimelock
+        );
+
+        require!(
+            switch.cancel_requested_at == 0,
+            ErrorCode::CancelAlreadyRequested
+        );
+
+        // A frozen cancel stays frozen until the voters thaw it
+        require!(!switch.cancel_frozen, ErrorCode::CancelFrozen);
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        switch.cancel_requested_at = clock.unix_timestamp;
+        switch.freeze_votes.clear();
+
+        let executable_at = clock.unix_timestamp.saturating_add(switch.cancel_delay_seconds);
+
+        emit!(CancelRequested {
+            switch: switch.key(),
+            requested_at: clock.unix_timestamp,
+            executable_at,
+        });
+
+        msg!("Cancel requested, executable at {}", executable_at);
+
+        Ok(())
+    }
+
+    /// Withdraw a pending cancel request, frozen or not. A frozen request keeps its
+    /// votes, and no new request can be made until the voters thaw it.
+    pub fn abort_cancel(ctx: Context<RequestCancel>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.cancel_requested_at > 0,
+            ErrorCode::CancelNotRequested
+        );
+
+        switch.cancel_requested_at = 0;
+        if !switch.cancel_frozen {
+            switch.freeze_votes.clear();
+        }
+
+        msg!("Cancel request aborted");
+
+        Ok(())
+    }
+
+    /// Guardian or beneficiary vote to freeze a pending cancel; the request is frozen
+    /// once `freeze_quorum` distinct voters agree
+    pub fn freeze_cancel(ctx: Context<FreezeCancel>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.cancel_requested_at > 0,
+            ErrorCode::CancelNotRequested
+        );
+
+        let voter = ctx.accounts.voter.key();
+        require!(
+            switch.guardians.contains(&voter)
+                || switch.beneficiaries.iter().any(|b| b.address == voter),
+            ErrorCode::NotGuardianOrBeneficiary
+        );
+
+        require!(
+            !switch.freeze_votes.contains(&voter),
+            ErrorCode::FreezeAlreadyVoted
+        );
+
+        switch.freeze_votes.push(voter);
+
+        if !switch.cancel_frozen && switch.freeze_votes.len() >= switch.freeze_quorum as usize {
+            switch.cancel_frozen = true;
+
+            emit!(CancelFrozen {
+                switch: switch.key(),
+                votes: switch.freeze_votes.clone(),
+            });
+
+            msg!("Pending cancel frozen");
+        }
+
+        Ok(())
+    }
+
+    /// Withdraw a freeze vote. The freeze is lifted once fewer than `freeze_quorum`
+    /// votes remain, letting the pending request run or the owner request again.
+    pub fn thaw_cancel(ctx: Context<FreezeCancel>, _switch_id: String) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        let voter = ctx.accounts.voter.key();
+        require!(
+            switch.freeze_votes.contains(&voter),
+            ErrorCode::FreezeVoteNotFound
+        );
+
+        switch.freeze_votes.retain(|key| key != &voter);
+
+        if switch.cancel_frozen && switch.freeze_votes.len() < switch.freeze_quorum as usize {
+            switch.cancel_frozen = false;
+
+            emit!(CancelThawed {
+                switch: switch.key(),
+                votes: switch.freeze_votes.clone(),
+            });
+
+            msg!("Cancel freeze lifted");
+        }
+
+        Ok(())
+    }
+
+    /// Cancel the switch so the owner can take the escrow back with `withdraw_sol`. The
+    /// switch stays open, since the escrow PDA needs it to sign. With a cancel timelock,
+    /// the cancel must have been requested at least the delay ago and not frozen.
+    /// Remaining accounts are the co-owner signers and the beneficiaries' index PDAs.
+    pub fn cancel_switch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelSwitch<'info>>,
+        switch_id: String,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        // Stake accounts and authorities can only be reclaimed while active
+        require!(
+            switch.stake_accounts.is_empty(),
+            ErrorCode::StakeAccountsRegistered
+        );
+        require!(
+            switch.held_authorities.is_empty(),
+            ErrorCode::AuthoritiesHeld
+        );
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        // The profile counts its linked switches, so unlink before closing
+        require!(switch.profile.is_none(), ErrorCode::SwitchLinkedToProfile);
+
+        switch.require_cancel_unlocked(Clock::get()?.unix_timestamp)?;
+
+        switch.status = SwitchStatus::Canceled;
+
+        remove_from_beneficiary_indexes(
+            switch.key(),
+            &switch.beneficiaries,
+            ctx.remaining_accounts,
+        )?;
+
+        msg!("Switch '{}' canceled by owner", switch_id);
+
+        Ok(())
+    }
+
+    /// Cap how much `withdraw_partial` can take out per period, or remove the cap.
+    /// Shorthand for proposing a withdrawal limit change: tightening applies at once,
+    /// loosening is staged for a full period or the config delay, whichever is longer.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn set_withdrawal_limit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetWithdrawalLimit<'info>>,
+        _switch_id: String,
+        limit: Option<WithdrawalLimit>,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        let change = ConfigChange::WithdrawalLimit { limit };
+
+        if !stage_config_change(switch, change.clone(), clock.unix_timestamp)? {
+            apply_config_change(
+                switch,
+                change,
+                ctx.remaining_accounts,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Withdraw part of the escrow while the switch stays active. Pass both token accounts
+    /// to withdraw SPL tokens, neither to withdraw SOL. Counts as a heartbeat unless the
+    /// switch is hash-chained or linked to a profile. With a cancel timelock, needs a
+    /// cancel request that has waited out the delay.
+    /// Remaining accounts are co-owner signers when an owner quorum is set.
+    pub fn withdraw_partial(
+        ctx: Context<WithdrawPartial>,
+        switch_id: String,
+        amount: u64,
+    ) -> Result<()> {
+        let switch = &mut ctx.accounts.switch;
+        let clock = Clock::get()?;
+
+        require!(amount > 0, ErrorCode::InsufficientFunds);
+
+        // Heartbeats pass under duress, withdrawals do not
+        require!(
+            switch.status == SwitchStatus::Active,
+            ErrorCode::SwitchNotActive
+        );
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        switch.require_cancel_unlocked(clock.unix_timestamp)?;
+
+        if switch.record_withdrawal(clock.unix_timestamp, ctx.accounts.profile.as_ref())? {
+            ctx.accounts.history.append(
+                ctx.accounts.owner.key(),
+                HeartbeatMethod::Direct,
+                &clock,
+            );
+        }
+
+        let is_spl = match (
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.owner_token_account,
+        ) {
+            (Some(escrow_token_account), Some(owner_token_account)) => {
+                require!(
+                    escrow_token_account.owner == ctx.accounts.escrow.key()
+                        && owner_token_account.mint == escrow_token_account.mint,
+                    ErrorCode::InvalidEscrowTokenAccount
+                );
+                require!(
+                    escrow_token_account.amount >= amount,
+                    ErrorCode::InsufficientFunds
+                );
+                true
+            }
+            (None, None) => {
+                let escrow_balance = ctx.accounts.escrow.lamports();
+                let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
+                require!(
+                    escrow_balance.saturating_sub(rent_exempt) >= amount,
+                    ErrorCode::InsufficientFunds
+                );
+                false
+            }
+            _ => return err!(ErrorCode::InvalidEscrowTokenAccount),
+        };
+
+        if let Some(limit) = switch.withdrawal_limit.clone() {
+            // Start a fresh period once the previous one has run out
+            if clock.unix_timestamp
+                >= switch
+                    .withdrawal_period_start
+                    .saturating_add(limit.period_seconds)
+            {
+                switch.withdrawal_period_start = clock.unix_timestamp;
+                switch.withdrawn_lamports = 0;
+                switch.withdrawn_tokens = 0;
+            }
+
+            let (withdrawn, cap) = if is_spl {
+                (&mut switch.withdrawn_tokens, limit.tokens_per_period)
+            } else {
+                (&mut switch.withdrawn_lamports, limit.lamports_per_period)
+            };
+            let total = withdrawn
+                .checked_add(amount)
+                .ok_or(ErrorCode::WithdrawalLimitExceeded)?;
+            require!(total <= cap, ErrorCode::WithdrawalLimitExceeded);
+            *withdrawn = total;
+        }
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        match (
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.owner_token_account,
+        ) {
+            (Some(escrow_token_account), Some(owner_token_account)) => {
+                let cpi_accounts = Transfer {
+                    from: escrow_token_account.to_account_info(),
+                    to: owner_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, amount)?;
+
+                msg!("Withdrew {} tokens ({}) to owner", amount, escrow_token_account.mint);
+            }
+            _ => {
+                let cpi_context = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    SystemTransfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.owner.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                transfer(cpi_context, amount)?;
+
+                msg!("Withdrew {} lamports to owner", amount);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Withdraw SOL after cancellation
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>, switch_id: String) -> Result<()> {
+        let switch = &ctx.accounts.switch;
+
+        require!(
+            switch.status == SwitchStatus::Canceled,
+            ErrorCode::SwitchNotCanceled
+        );
+
+        require!(
+            switch.owner_signatures(ctx.remaining_accounts) >= switch.owner_quorum as usize,
+            ErrorCode::OwnerQuorumNotMet
+        );
+
+        let escrow_balance = ctx.accounts.escrow.lamports();
+        let rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.escrow.data_len());
+        let withdrawable = escrow_balance.saturating_sub(rent_exempt);
+
+        require!(withdrawable > 0, ErrorCode::InsufficientFunds);
+
+        let owner_key = switch.owner;
+        let switch_id_bytes = switch_id.as_bytes();
+        let escrow_bump = ctx.bumps.escrow;
+        let seeds = &[
+            b"escrow",
+            owner_key.as_ref(),
+            switch_id_bytes,
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer(cpi_context, withdrawable)?;
+
+        msg!("Withdrawn {} lamports to owner", withdrawable);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Add `switch` to the reverse index of every beneficiary, creating or growing
+/// the index PDAs passed in `remaining_accounts` at the payer's expense
+fn add_to_beneficiary_indexes<'info>(
+    switch: Pubkey,
+    beneficiaries: &[Beneficiary],
+    remaining_accounts: &'info [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?;
+
+    for (i, beneficiary) in beneficiaries.iter().enumerate() {
+        // A beneficiary listed twice only needs one entry
+        if beneficiaries[..i].iter().any(|b| b.address == beneficiary.address) {
+            continue;
+        }
+
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"beneficiary_index", beneficiary.address.as_ref()],
+            &crate::ID,
+        );
+        let info = remaining_accounts
+            .iter()
+            .find(|account| account.key() == expected && account.is_writable)
+            .ok_or(ErrorCode::BeneficiaryIndexMissing)?;
+
+        if info.owner == &crate::ID {
+            let mut index = Account::<BeneficiaryIndex>::try_from(info)?;
+            if index.switches.contains(&switch) {
+                continue;
+            }
+            index.switches.push(switch);
+
+            let space = BeneficiaryIndex::space(index.switches.len());
+            if info.data_len() < space {
+                let top_up = rent.minimum_balance(space).saturating_sub(info.lamports());
+                if top_up > 0 {
+                    let cpi_context = CpiContext::new(
+                        system_program.clone(),
+                        SystemTransfer {
+                            from: payer.clone(),
+                            to: info.clone(),
+                        },
+                    );
+                    transfer(cpi_context, top_up)?;
+                }
+                info.resize(space)?;
+            }
+
+            index.exit(&crate::ID)?;
+            continue;
+        }
+
+        let space = BeneficiaryIndex::space(1);
+        let lamports = rent.minimum_balance(space);
+        let seeds: &[&[u8]] = &[b"beneficiary_index", beneficiary.address.as_ref(), &[bump]];
+        let signer_seeds = &[seeds];
+
+        if info.lamports() == 0 {
+            let cpi_context = CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                signer_seeds,
+            );
+            create_account(cpi_context, lamports, space as u64, &crate::ID)?;
+        } else {
+            // Someone pre-funded the PDA, so create_account would fail
+            let top_up = lamports.saturating_sub(info.lamports());
+            if top_up > 0 {
+                let cpi_context = CpiContext::new(
+                    system_program.clone(),
+                    SystemTransfer {
+                        from: payer.clone(),
+                        to: info.clone(),
+                    },
+                );
+                transfer(cpi_context, top_up)?;
+            }
+            let cpi_context = CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: info.clone(),
+                },
+                signer_seeds,
+            );
+            allocate(cpi_context, space as u64)?;
+            let cpi_context = CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign {
+                    account_to_assign: info.clone(),
+                },
+                signer_seeds,
+            );
+            assign(cpi_context, &crate::ID)?;
+        }
+
+        let index = BeneficiaryIndex {
+            beneficiary: beneficiary.address,
+            switches: vec![switch],
+            bump,
+        };
+        let mut data = info.try_borrow_mut_data()?;
+        index.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+/// Drop `switch` from the reverse index of every beneficiary before it is closed
+fn remove_from_beneficiary_indexes<'info>(
+    switch: Pubkey,
+    beneficiaries: &[Beneficiary],
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    for (i, beneficiary) in beneficiaries.iter().enumerate() {
+        if beneficiaries[..i].iter().any(|b| b.address == beneficiary.address) {
+            continue;
+        }
+
+        // Only this program can write the index discriminator, so ownership
+        // plus a matching beneficiary identifies the PDA
+        let mut index = remaining_accounts
+            .iter()
+            .filter(|account| account.owner == &crate::ID && account.is_writable)
+            .filter_map(|account| Account::<BeneficiaryIndex>::try_from(account).ok())
+            .find(|index| index.beneficiary == beneficiary.address)
+            .ok_or(ErrorCode::BeneficiaryIndexMissing)?;
+
+        index.switches.retain(|key| key != &switch);
+        index.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Validate `change` and queue it behind the config delay. Returns false when no
+/// delay is set and the caller should apply the change right away.
+fn stage_config_change(switch: &mut Account<Switch>, change: ConfigChange, now: i64) -> Result<bool> {
+    require!(
+        switch.status == SwitchStatus::Active,
+        ErrorCode::SwitchNotActive
+    );
+
+    require!(
+        switch.pending_config_change.is_none(),
+        ErrorCode::ConfigChangePending
+    );
+
+    switch.validate_config_change(&change)?;
+
+    let delay_seconds = switch.config_change_delay(&change);
+    if delay_seconds == 0 {
+        return Ok(false);
+    }
+
+    // Loosening the withdrawal limit is as good as a withdrawal
+    if matches!(change, ConfigChange::WithdrawalLimit { .. }) {
+        switch.require_cancel_unlocked(now)?;
+    }
+
+    let activates_at = now.saturating_add(delay_seconds);
+    switch.pending_config_change = Some(PendingConfigChange {
+        change: change.clone(),
+        proposed_at: now,
+        activates_at,
+    });
+
+    emit!(ConfigChangeProposed {
+        switch: switch.key(),
+        change,
+        activates_at,
+    });
+
+    msg!("Config change staged, activates at {}", activates_at);
+
+    Ok(true)
+}
+
+/// Write `change` to the switch. A beneficiary change also moves the switch between
+/// beneficiary indexes, creating new index PDAs at the payer's expense.
+fn apply_config_change<'info>(
+    switch: &mut Account<'info, Switch>,
+    change: ConfigChange,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    match &change {
+        ConfigChange::Beneficiaries { beneficiaries } => {
+            let removed: Vec<Beneficiary> = switch
+                .beneficiaries
+                .iter()
+                .filter(|old| !beneficiaries.iter().any(|b| b.address == old.address))
+                .cloned()
+                .collect();
+            remove_from_beneficiary_indexes(switch.key(), &removed, remaining_accounts)?;
+            add_to_beneficiary_indexes(
+                switch.key(),
+                beneficiaries,
+                remaining_accounts,
+                payer,
+                system_program,
+            )?;
+
+            switch.beneficiaries = beneficiaries.clone();
+            // Executor adjustments and ordering refer to beneficiaries by index
+            switch.share_adjustments.clear();
+            switch.distribution_order.clear();
+        }
+        ConfigChange::Timeout { timeout_seconds } => {
+            // The current deadline stands, the new timeout applies from the next heartbeat
+            switch.timeout_seconds = *timeout_seconds;
+        }
+        ConfigChange::Fallback {
+            fallback,
+            claim_deadline_seconds,
+        } => {
+            switch.fallback = Some(*fallback);
+            switch.claim_deadline_seconds = *claim_deadline_seconds;
+        }
+        ConfigChange::HeartbeatSchedule { schedule } => {
+            // Like a timeout change, the current deadline stands until the next heartbeat
+            switch.heartbeat_schedule = *schedule;
+        }
+        ConfigChange::ChallengePeriod {
+            challenge_period_seconds,
+        } => {
+            switch.challenge_period_seconds = *challenge_period_seconds;
+        }
+        ConfigChange::Executor {
+            executor,
+            max_share_adjustment_bps,
+            executor_window_seconds,
+            expenses,
+        } => {
+            switch.executor = *executor;
+            switch.max_share_adjustment_bps = *max_share_adjustment_bps;
+            switch.executor_window_seconds = *executor_window_seconds;
+            switch.approved_expenses = expenses
+                .iter()
+                .map(|e| ApprovedExpense {
+                    paid_lamports: 0,
+                    ..e.clone()
+                })
+                .collect();
+        }
+        ConfigChange::ExpiryActions { actions_hash } => {
+            switch.expiry_actions_hash = Some(*actions_hash);
+        }
+        ConfigChange::WithdrawalLimit { limit } => {
+            // Keep counting against the running period even if its length changes, or
+            // resetting the period would hand out a fresh allowance every time
+            switch.withdrawal_limit = limit.clone();
+        }
+    }
+
+    switch.pending_config_change = None;
+
+    emit!(ConfigChangeApplied {
+        switch: switch.key(),
+        change,
+    });
+
+    msg!("Config change applied");
+
+    Ok(())
+}
+
+/// Message the secp256r1 precompile verified for `public_key` in the instruction right
+/// before the current one. Offsets must point into the precompile's own data.
+fn secp256r1_verified_message(
+    instructions: &AccountInfo,
+    public_key: &[u8; 33],
+) -> Result<Vec<u8>> {
+    let (precompile_index, ix) = preceding_instruction(instructions, &SECP256R1_PROGRAM_ID)?;
+
+    // num_signatures, padding, then one 14-byte offsets struct
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        ErrorCode::InvalidSignatureVerification
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    for instruction_index in [read_u16(4), read_u16(8), read_u16(14)] {
+        require!(
+            instruction_index == u16::MAX || instruction_index == precompile_index,
+            ErrorCode::InvalidSignatureVerification
+        );
+    }
+
+    let verified_key = data
+        .get(public_key_offset..public_key_offset + 33)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+    require!(
+        verified_key == public_key.as_slice(),
+        ErrorCode::InvalidSignatureVerification
+    );
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+
+    Ok(message.to_vec())
+}
+
+/// Message the secp256k1 precompile verified as signed by `eth_address` in the instruction
+/// right before the current one. Offsets must point into the precompile's own data.
+fn secp256k1_verified_message(
+    instructions: &AccountInfo,
+    eth_address: &[u8; 20],
+) -> Result<Vec<u8>> {
+    let (precompile_index, ix) = preceding_instruction(instructions, &secp256k1_program::ID)?;
+
+    // num_signatures, then one 11-byte offsets struct with u8 instruction indexes
+    let data = &ix.data;
+    require!(
+        data.len() >= 12 && data[0] == 1,
+        ErrorCode::InvalidSignatureVerification
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+
+    let eth_address_offset = read_u16(4);
+    let message_offset = read_u16(7);
+    let message_size = read_u16(9);
+    for instruction_index in [data[3], data[6], data[11]] {
+        require!(
+            instruction_index as u16 == precompile_index,
+            ErrorCode::InvalidSignatureVerification
+        );
+    }
+
+    let verified_address = data
+        .get(eth_address_offset..eth_address_offset + 20)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+    require!(
+        verified_address == eth_address.as_slice(),
+        ErrorCode::InvalidSignatureVerification
+    );
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+
+    Ok(message.to_vec())
+}
+
+/// The instruction right before the current one, which must be a call to `program_id`
+fn preceding_instruction(
+    instructions: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<(u16, Instruction)> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::MissingSignatureVerification);
+
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(
+        ix.program_id,
+        *program_id,
+        ErrorCode::MissingSignatureVerification
+    );
+
+    Ok((current - 1, ix))
+}
+
+/// Text an Ethereum key signs to send a heartbeat to `switch`
+fn eth_heartbeat_message(switch: &Pubkey, nonce: u64) -> String {
+    format!("Dead man's switch heartbeat\nSwitch: {}\nNonce: {}", switch, nonce)
+}
+
+/// EIP-191 personal_sign encoding; the precompile hashes it with keccak256
+fn eip191_message(text: &str) -> Vec<u8> {
+    format!("\x19Ethereum Signed Message:\n{}{}", text.len(), text).into_bytes()
+}
+
+/// Unpadded base64url, as WebAuthn encodes challenges in the client data
+fn base64url_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, byte)| acc | (*byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+/// Months since January 1970 of a UTC timestamp
+fn month_index(timestamp: i64) -> i64 {
+    // Civil-from-days conversion, days counted from 0000-03-01
+    let days = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // March = 0
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year - 1970) * 12 + (month - 1)
+}
+
+/// UTC timestamp of the first second of the month `month_index` months after January 1970
+fn month_start(month_index: i64) -> i64 {
+    // Days-from-civil conversion with years starting in March
+    let month = month_index.rem_euclid(12) + 1;
+    let year = 1970 + month_index.div_euclid(12) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * SECONDS_PER_DAY
+}
+
+/// Move a held authority from `current` to `new` through the SPL token program
+/// or the upgradeable BPF loader
+fn set_held_authority<'info>(
+    held: &HeldAuthority,
+    target_account: &AccountInfo<'info>,
+    current: &AccountInfo<'info>,
+    new: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    bpf_loader_upgradeable: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let authority_type = match held.kind {
+        AuthorityKind::ProgramUpgrade => {
+            let ix = bpf_loader_upgradeable::set_upgrade_authority(
+                &held.target,
+                current.key,
+                Some(new.key),
+            );
+            invoke_signed(
+                &ix,
+                &[
+                    target_account.clone(),
+                    current.clone(),
+                    new.clone(),
+                    bpf_loader_upgradeable.clone(),
+                ],
+                signer_seeds,
+            )?;
+            return Ok(());
+        }
+        AuthorityKind::MintTokens => AuthorityType::MintTokens,
+        AuthorityKind::FreezeAccount => AuthorityType::FreezeAccount,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        SetAuthority {
+            current_authority: current.clone(),
+            account_or_mint: target_account.clone(),
+        },
+        signer_seeds,
+    );
+    token::set_authority(cpi_ctx, authority_type, Some(*new.key))
+}
+
+// ============================================================================
+// Account Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct InitializeSwitch<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Switch::INIT_SPACE,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HeartbeatHistory::INIT_SPACE,
+        seeds = [b"history", switch.key().as_ref()],
+        bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// CHECK: PDA for holding escrow funds
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    
+    /// Owner may be a PDA signing through CPI, so it does not pay rent
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Registry::INIT_SPACE,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeIndexedSwitch<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump = registry.bump,
+        has_one = owner
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Switch::INIT_SPACE,
+        seeds = [b"switch", owner.key().as_ref(), registry.next_switch_id().as_bytes()],
+        bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HeartbeatHistory::INIT_SPACE,
+        seeds = [b"history", switch.key().as_ref()],
+        bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// CHECK: PDA for holding escrow funds
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), registry.next_switch_id().as_bytes()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SendHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetDuressCode<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ClearDuress<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetPasskey<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SendPasskeyHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// CHECK: Instructions sysvar, read for the secp256r1 precompile instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetEthAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SendEthHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// CHECK: Instructions sysvar, read for the secp256k1 precompile instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetHeartbeatChain<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SendHeartbeatBatch<'info> {
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetJointOwners<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SendCoOwnerHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// Profile the switch is linked to, if any
+    pub profile: Option<Account<'info, Profile>>,
+    
+    pub co_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProfile<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SendProfileHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct LinkProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetFallback<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetConfigDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ProposeConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    /// Pays for new beneficiary index PDAs when the change applies at once
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ApplyConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    /// Profile the switch is linked to, if any
+    pub profile: Option<Account<'info, Profile>>,
+    
+    /// Pays for new beneficiary index PDAs
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetChallengePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetMinHeartbeatInterval<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetHeartbeatSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ChallengeLiveness<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    /// Profile the switch is linked to, if any
+    pub profile: Option<Account<'info, Profile>>,
+    
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct TriggerExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    /// Profile the switch is linked to, if any
+    #[account(mut)]
+    pub profile: Option<Account<'info, Profile>>,
+}
+
+#[derive(Accounts)]
+pub struct GetStatus<'info> {
+    #[account(
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    /// Escrow token account, only read for SPL switches
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Profile the switch is linked to, if any
+    pub profile: Option<Account<'info, Profile>>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ReviveSwitch<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    /// Profile the switch is linked to, if any
+    #[account(mut)]
+    pub profile: Option<Account<'info, Profile>>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetExecutor<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutorAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump,
+        constraint = switch.executor == Some(executor.key()) @ ErrorCode::NotExecutor
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutorPayExpense<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump,
+        constraint = switch.executor == Some(executor.key()) @ ErrorCode::NotExecutor
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Checked against the switch's approved expenses
+    pub payee: UncheckedAccount<'info>,
+    
+    pub executor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Beneficiary receiving funds
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Beneficiary receiving funds
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Optional token account for escrow (for SPL transfers)
+    pub escrow_token_account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Beneficiary receiving funds
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Optional token account for beneficiary (for SPL transfers)
+    pub beneficiary_token_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
 
-        require!(withdrawable > 0, ErrorCode::InsufficientFunds);
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = switch.fallback == Some(fallback.key()) @ ErrorCode::InvalidFallback
+    )]
+    /// CHECK: Fallback address recorded on the switch
+    pub fallback: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"actions", switch.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Stored expiry actions PDA, closed if it exists
+    pub expiry_actions: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Original owner, receives the switch account rent
+    pub owner: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
 
-        let owner_key = switch.owner;
-        let switch_id_bytes = switch_id.as_bytes();
-        let escrow_bump = ctx.bumps.escrow;
-        let seeds = &[
-            b"escrow",
-            owner_key.as_ref(),
-            switch_id_bytes,
-            &[escrow_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct DepositStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, becomes the stake authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub stake_program: Program<'info, Stake>,
+}
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            SystemTransfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.owner.to_account_info(),
-            },
-            signer_seeds,
-        );
+#[derive(Accounts)]
+pub struct ReleaseStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, current stake authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    
+    /// CHECK: Beneficiary registered for this stake account
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub stake_program: Program<'info, Stake>,
+}
 
-        transfer(cpi_context, withdrawable)?;
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ReclaimStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, current stake authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub stake_program: Program<'info, Stake>,
+}
 
-        msg!("Withdrawn {} lamports to owner", withdrawable);
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct DepositAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, becomes the authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Mint, or program data account for upgrade authorities
+    pub target_account: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Upgradeable BPF loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
+}
 
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct ReleaseAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, current authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Mint, or program data account for upgrade authorities
+    pub target_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Beneficiary registered for this authority
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Upgradeable BPF loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
 }
 
-// ============================================================================
-// Account Structs
-// ============================================================================
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ReclaimAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, current authority
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Mint, or program data account for upgrade authorities
+    pub target_account: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Upgradeable BPF loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
+}
 
 #[derive(Accounts)]
 #[instruction(switch_id: String)]
-pub struct InitializeSwitch<'info> {
+pub struct StoreExpiryActions<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, the only permitted action signer
+    pub escrow: UncheckedAccount<'info>,
+    
     #[account(
         init,
         payer = owner,
-        space = 8 + Switch::INIT_SPACE,
-        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        space = 8 + ExpiryActions::INIT_SPACE,
+        seeds = [b"actions", switch.key().as_ref()],
         bump
     )]
+    pub expiry_actions: Account<'info, ExpiryActions>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct ClearExpiryActions<'info> {
+    #[account(
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
+    )]
     pub switch: Account<'info, Switch>,
     
-    /// CHECK: PDA for holding escrow funds
     #[account(
-        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
-        bump
+        mut,
+        close = owner,
+        seeds = [b"actions", switch.key().as_ref()],
+        bump = expiry_actions.bump,
+        has_one = switch
+    )]
+    pub expiry_actions: Account<'info, ExpiryActions>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteExpiryAction<'info> {
+    #[account(
+        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump = switch.bump
+    )]
+    pub switch: Account<'info, Switch>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        bump
+    )]
+    /// CHECK: PDA escrow account, signs the action
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"actions", switch.key().as_ref()],
+        bump = expiry_actions.bump,
+        has_one = switch
+    )]
+    pub expiry_actions: Account<'info, ExpiryActions>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetCancelTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
     )]
-    pub escrow: UncheckedAccount<'info>,
+    pub switch: Account<'info, Switch>,
     
-    #[account(mut)]
     pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 #[instruction(switch_id: String)]
-pub struct SendHeartbeat<'info> {
+pub struct RequestCancel<'info> {
     #[account(
         mut,
         seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
@@ -468,154 +4540,734 @@ pub struct SendHeartbeat<'info> {
 
 #[derive(Accounts)]
 #[instruction(switch_id: String)]
-pub struct TriggerExpiry<'info> {
+pub struct FreezeCancel<'info> {
     #[account(
         mut,
         seeds = [b"switch", switch.owner.as_ref(), switch_id.as_bytes()],
         bump = switch.bump
     )]
     pub switch: Account<'info, Switch>,
+    
+    /// Guardian or beneficiary
+    pub voter: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct DistributeSol<'info> {
+#[instruction(switch_id: String)]
+pub struct CancelSwitch<'info> {
     #[account(
-        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
-        bump = switch.bump
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
     )]
     pub switch: Account<'info, Switch>,
     
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct SetWithdrawalLimit<'info> {
     #[account(
         mut,
-        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
-        bump
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
     )]
-    /// CHECK: PDA escrow account
-    pub escrow: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    /// CHECK: Beneficiary receiving funds
-    pub beneficiary: UncheckedAccount<'info>,
+    pub switch: Account<'info, Switch>,
     
+    pub owner: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DistributeSpl<'info> {
+#[instruction(switch_id: String)]
+pub struct WithdrawPartial<'info> {
     #[account(
-        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
-        bump = switch.bump
+        mut,
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
     )]
     pub switch: Account<'info, Switch>,
     
     #[account(
-        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, HeartbeatHistory>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
         bump
     )]
     /// CHECK: PDA escrow account
     pub escrow: UncheckedAccount<'info>,
     
+    /// Escrow's token account, for SPL withdrawals
     #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
     
-    /// CHECK: Beneficiary receiving funds
-    pub beneficiary: UncheckedAccount<'info>,
+    /// Destination token account, for SPL withdrawals
+    #[account(mut)]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Profile the switch is linked to, if any
+    pub profile: Option<Account<'info, Profile>>,
     
     #[account(mut)]
-    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
     
+    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct DistributeAsset<'info> {
+#[instruction(switch_id: String)]
+pub struct WithdrawSol<'info> {
     #[account(
-        seeds = [b"switch", switch.owner.as_ref(), switch.switch_id.as_bytes()],
-        bump = switch.bump
+        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
+        bump = switch.bump,
+        has_one = owner
     )]
     pub switch: Account<'info, Switch>,
     
     #[account(
         mut,
-        seeds = [b"escrow", switch.owner.as_ref(), switch.switch_id.as_bytes()],
+        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
         bump
     )]
     /// CHECK: PDA escrow account
     pub escrow: UncheckedAccount<'info>,
     
     #[account(mut)]
-    /// CHECK: Optional token account for escrow (for SPL transfers)
-    pub escrow_token_account: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    /// CHECK: Beneficiary receiving funds
-    pub beneficiary: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    /// CHECK: Optional token account for beneficiary (for SPL transfers)
-    pub beneficiary_token_account: UncheckedAccount<'info>,
+    pub owner: Signer<'info>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
 }
 
-#[derive(Accounts)]
-#[instruction(switch_id: String)]
-pub struct CancelSwitch<'info> {
-    #[account(
-        mut,
-        close = owner,
-        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
-        bump = switch.bump,
-        has_one = owner
-    )]
-    pub switch: Account<'info, Switch>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+// ============================================================================
+// State
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Switch {
+    pub owner: Pubkey,                              // 32
+    #[max_len(MAX_SWITCH_ID_LEN)]
+    pub switch_id: String,                          // 4 + 32 = 36
+    #[max_len(MAX_BENEFICIARIES)]
+    pub beneficiaries: Vec<Beneficiary>,            // 4 + (10 * 34) = 344
+    pub token_type: TokenType,                      // 1 + 32 = 33
+    pub timeout_seconds: i64,                       // 8
+    pub heartbeat_deadline: i64,                    // 8
+    pub status: SwitchStatus,                       // 1
+    pub bump: u8,                                   // 1
+    pub fallback: Option<Pubkey>,                   // 1 + 32 = 33
+    pub claim_deadline_seconds: i64,                // 8
+    pub expired_at: i64,                            // 8
+    pub challenge_period_seconds: i64,              // 8
+    #[max_len(MAX_CO_OWNERS)]
+    pub co_owners: Vec<CoOwner>,                    // 4 + (5 * 40) = 204
+    pub expiry_policy: ExpiryPolicy,                // 1 + 1 = 2
+    pub owner_quorum: u8,                           // 1
+    #[max_len(MAX_STAKE_ACCOUNTS)]
+    pub stake_accounts: Vec<StakeInheritance>,      // 4 + (5 * 64) = 324
+    #[max_len(MAX_HELD_AUTHORITIES)]
+    pub held_authorities: Vec<HeldAuthority>,       // 4 + (5 * 65) = 329
+    pub profile: Option<Pubkey>,                    // 1 + 32 = 33
+    #[max_len(MAX_LABEL_LEN)]
+    pub label: String,                              // 4 + 64 = 68
+    pub heartbeat_schedule: HeartbeatSchedule,      // 1 + 16 = 17
+    pub last_heartbeat_at: i64,                     // 8
+    pub heartbeat_count: u64,                       // 8
+    pub min_heartbeat_interval_seconds: i64,        // 8
+    pub liveness_challenged_at: i64,                // 8, 0 if never challenged
+    #[max_len(MAX_BENEFICIARIES)]
+    pub liveness_challenges: Vec<LivenessChallenge>, // 4 + (10 * 40) = 404
+    pub executor: Option<Pubkey>,                   // 1 + 32 = 33
+    pub max_share_adjustment_bps: u16,              // 2
+    pub executor_window_seconds: i64,               // 8
+    #[max_len(MAX_APPROVED_EXPENSES)]
+    pub approved_expenses: Vec<ApprovedExpense>,    // 4 + (5 * 48) = 244
+    #[max_len(MAX_BENEFICIARIES)]
+    pub share_adjustments: Vec<i16>,                // 4 + (10 * 2) = 24, empty until adjusted
+    #[max_len(MAX_BENEFICIARIES)]
+    pub distribution_order: Vec<u8>,                // 4 + 10 = 14, beneficiary indices
+    pub distributions_made: u8,                     // 1, position in distribution_order
+    pub executor_finalized: bool,                   // 1
+    pub withdrawal_limit: Option<WithdrawalLimit>,  // 1 + 24 = 25
+    pub withdrawal_period_start: i64,               // 8
+    pub withdrawn_lamports: u64,                    // 8, in the current period
+    pub withdrawn_tokens: u64,                      // 8, in the current period
+    pub cancel_delay_seconds: i64,                  // 8, 0 if cancel is not timelocked
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,                     // 4 + (5 * 32) = 164
+    pub freeze_quorum: u8,                          // 1
+    pub cancel_requested_at: i64,                   // 8, 0 if no cancel is pending
+    #[max_len(MAX_GUARDIANS + MAX_BENEFICIARIES)]
+    pub freeze_votes: Vec<Pubkey>,                  // 4 + (15 * 32) = 484
+    pub cancel_frozen: bool,                        // 1
+    pub config_delay_seconds: i64,                  // 8, 0 applies config changes at once
+    pub pending_config_change: Option<PendingConfigChange>, // 1 + 345 + 16 = 362
+    pub heartbeat_chain: Option<[u8; 32]>,          // 1 + 32 = 33, latest revealed preimage
+    pub duress_code_hash: Option<[u8; 32]>,         // 1 + 32 = 33, sha256(switch || code)
+    pub duress_at: i64,                             // 8, 0 unless under duress
+    #[max_len(MAX_GUARDIANS)]
+    pub duress_clear_votes: Vec<Pubkey>,            // 4 + (5 * 32) = 164
+    pub canary: Option<Canary>,                     // 1 + 180 = 181, warrant canaries only
+    pub passkey: Option<[u8; 33]>,                  // 1 + 33 = 34, compressed secp256r1 key
+    pub passkey_nonce: u64,                         // 8, consumed by each passkey heartbeat
+    pub eth_address: Option<[u8; 20]>,              // 1 + 20 = 21
+    pub eth_nonce: u64,                             // 8, consumed by each Ethereum heartbeat
+    pub expiry_actions_hash: Option<[u8; 32]>,      // 1 + 32 = 33, approved by a config change
+}
+
+impl Switch {
+    /// Record a heartbeat that needs no preimage, which hash-chained switches reject
+    pub fn record_heartbeat(&mut self, now: i64) -> Result<()> {
+        // A stolen owner key alone must not keep a chained switch alive
+        require!(
+            self.heartbeat_chain.is_none(),
+            ErrorCode::HeartbeatPreimageRequired
+        );
+
+        self.extend_deadline(now)
+    }
+
+    /// Record a heartbeat that reveals the preimage of the last committed chain value,
+    /// which becomes the new commitment
+    pub fn record_chained_heartbeat(&mut self, now: i64, preimage: [u8; 32]) -> Result<()> {
+        self.reveal_chain_preimage(preimage)?;
+        self.extend_deadline(now)
+    }
+
+    /// Check `preimage` against the committed chain value and make it the new commitment
+    fn reveal_chain_preimage(&mut self, preimage: [u8; 32]) -> Result<()> {
+        let committed = self
+            .heartbeat_chain
+            .ok_or(ErrorCode::HeartbeatChainNotSet)?;
+
+        require!(
+            hash(&preimage).to_bytes() == committed,
+            ErrorCode::InvalidHeartbeatPreimage
+        );
+
+        self.heartbeat_chain = Some(preimage);
+
+        Ok(())
+    }
+
+    /// Record a profile heartbeat on a linked switch, held to the switch's minimum
+    /// interval like a direct heartbeat. `last_profile_heartbeat_at` is when the profile
+    /// last took a heartbeat.
+    pub fn record_profile_heartbeat(
+        &mut self,
+        now: i64,
+        last_profile_heartbeat_at: i64,
+    ) -> Result<()> {
+        require!(self.is_live(), ErrorCode::SwitchNotActive);
+
+        require!(
+            now >= self.last_heartbeat_at.saturating_add(self.min_heartbeat_interval_seconds)
+                || self.liveness_challenged_at > last_profile_heartbeat_at,
+            ErrorCode::HeartbeatTooSoon
+        );
+
+        self.last_heartbeat_at = now;
+        self.heartbeat_count += 1;
+
+        Ok(())
+    }
+
+    /// Extend the primary owner's deadline, rejecting inactive, expired or profile-linked
+    /// switches and heartbeats inside the minimum interval
+    fn extend_deadline(&mut self, now: i64) -> Result<()> {
+        // Duress must not show in how heartbeats behave
+        require!(self.is_live(), ErrorCode::SwitchNotActive);
+
+        // A linked switch follows its profile's deadline instead
+        require!(
+            self.profile.is_none(),
+            ErrorCode::SwitchLinkedToProfile
+        );
+
+        // Check if deadline has already passed - if so, reject heartbeat
+        require!(
+            now <= self.effective_deadline(None)?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        // Heartbeats closer together than the minimum interval carry no extra signal,
+        // except the one answering a pending liveness challenge
+        require!(
+            now >= self.last_heartbeat_at.saturating_add(self.min_heartbeat_interval_seconds)
+                || self.liveness_challenged_at > self.last_heartbeat_at,
+            ErrorCode::HeartbeatTooSoon
+        );
+
+        // Update deadline only if switch is still active and not expired
+        self.refresh_deadline(now);
+
+        Ok(())
+    }
+
+    /// Check that an owner withdrawal comes before the deadline and count it as a heartbeat
+    /// where one needs nothing more than the owner's signature. Returns whether it counted:
+    /// a chained switch needs a preimage and a linked switch follows its profile, so there
+    /// it is a withdrawal only. The minimum interval does not apply.
+    pub fn record_withdrawal(
+        &mut self,
+        now: i64,
+        profile: Option<&Account<Profile>>,
+    ) -> Result<bool> {
+        require!(
+            now <= self.effective_deadline(profile)?,
+            ErrorCode::SwitchAlreadyExpired
+        );
+
+        if self.heartbeat_chain.is_some() || self.profile.is_some() {
+            return Ok(false);
+        }
+
+        self.refresh_deadline(now);
+
+        Ok(true)
+    }
+
+    fn refresh_deadline(&mut self, now: i64) {
+        self.heartbeat_deadline = self.next_deadline(now);
+        self.last_heartbeat_at = now;
+        self.heartbeat_count += 1;
+    }
+
+    /// Whether this is a warrant canary whose current statement still stands: renewed
+    /// before its deadline and never lapsed. Needs nothing beyond the switch account.
+    pub fn canary_is_fresh(&self, now: i64) -> Result<bool> {
+        let canary = self.canary.as_ref().ok_or(ErrorCode::NotCanary)?;
+        Ok(self.is_live() && canary.lapsed_at == 0 && now <= self.effective_deadline(None)?)
+    }
+
+    /// Whether heartbeats and expiry still apply: active, or active under duress
+    pub fn is_live(&self) -> bool {
+        matches!(self.status, SwitchStatus::Active | SwitchStatus::Duress)
+    }
+
+    /// Deadline earned by a heartbeat at `heartbeat_at` under the switch's schedule
+    pub fn next_deadline(&self, heartbeat_at: i64) -> i64 {
+        self.heartbeat_schedule
+            .deadline_after(heartbeat_at, self.timeout_seconds)
+    }
+
+    /// End of the window after expiry in which the owner can still revive the switch
+    pub fn challenge_deadline(&self) -> i64 {
+        self.expired_at.saturating_add(self.challenge_period_seconds)
+    }
+
+    /// Beneficiary's share including any executor adjustment
+    pub fn share_bps(&self, index: usize) -> u16 {
+        let adjustment = self.share_adjustments.get(index).copied().unwrap_or(0);
+        (self.beneficiaries[index].share_bps as i32 + adjustment as i32) as u16
+    }
+
+    /// Whether the executor may still act, which holds distribution back
+    pub fn executor_window_open(&self, now: i64) -> bool {
+        self.executor.is_some()
+            && !self.executor_finalized
+            && now <= self.challenge_deadline().saturating_add(self.executor_window_seconds)
+    }
+
+    /// Executor actions need an expired switch past its challenge period and an open window
+    pub fn require_executor_window(&self, now: i64) -> Result<()> {
+        require!(
+            self.status == SwitchStatus::Expired,
+            ErrorCode::SwitchNotExpired
+        );
+        require!(
+            now > self.challenge_deadline(),
+            ErrorCode::ChallengePeriodActive
+        );
+        require!(
+            self.executor_window_open(now),
+            ErrorCode::ExecutorWindowClosed
+        );
+        Ok(())
+    }
+
+    /// Answer-by time of the latest liveness challenge, or None once the owner has
+    /// sent a heartbeat (directly or through a linked profile) since it was raised
+    pub fn liveness_challenge_deadline(
+        &self,
+        profile: Option<&Account<Profile>>,
+    ) -> Result<Option<i64>> {
+        if self.liveness_challenged_at == 0 {
+            return Ok(None);
+        }
+
+        let last_heartbeat_at = match self.profile {
+            Some(linked) => {
+                let profile = profile.ok_or(ErrorCode::ProfileRequired)?;
+                require_keys_eq!(profile.key(), linked, ErrorCode::ProfileMismatch);
+                profile.heartbeat_deadline - profile.timeout_seconds
+            }
+            None => self.last_heartbeat_at,
+        };
+
+        if last_heartbeat_at >= self.liveness_challenged_at {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.liveness_challenged_at
+                .saturating_add(LIVENESS_CHALLENGE_SECONDS),
+        ))
+    }
+
+    /// Deadline after which the switch can be expired, taking a linked profile,
+    /// co-owners and the expiry policy into account
+    pub fn effective_deadline(&self, profile: Option<&Account<Profile>>) -> Result<i64> {
+        // A linked profile's deadline stands in for the primary owner's own
+        let mut owner_deadline = match self.profile {
+            Some(linked) => {
+                let profile = profile.ok_or(ErrorCode::ProfileRequired)?;
+                require_keys_eq!(profile.key(), linked, ErrorCode::ProfileMismatch);
+                profile.heartbeat_deadline
+            }
+            None => self.heartbeat_deadline,
+        };
+
+        // An unanswered liveness challenge can only bring the deadline forward
+        if let Some(challenge_deadline) = self.liveness_challenge_deadline(profile)? {
+            owner_deadline = owner_deadline.min(challenge_deadline);
+        }
+
+        if self.co_owners.is_empty() {
+            return Ok(owner_deadline);
+        }
+
+        let mut deadlines: Vec<i64> = self
+            .co_owners
+            .iter()
+            .map(|c| self.next_deadline(c.last_heartbeat_at))
+            .collect();
+        deadlines.push(owner_deadline);
+        deadlines.sort_unstable();
+
+        // The switch expires once this many owners have missed their deadline
+        let missed = match self.expiry_policy {
+            ExpiryPolicy::Any => 1,
+            ExpiryPolicy::All => deadlines.len(),
+            ExpiryPolicy::Threshold { missed } => missed as usize,
+        };
+
+        Ok(deadlines[missed.clamp(1, deadlines.len()) - 1])
+    }
+
+    /// Check a staged config change against the current configuration
+    pub fn validate_config_change(&self, change: &ConfigChange) -> Result<()> {
+        match change {
+            ConfigChange::Beneficiaries { beneficiaries } => {
+                require!(self.canary.is_none(), ErrorCode::NotForCanary);
+
+                require!(
+                    !beneficiaries.is_empty() && beneficiaries.len() <= MAX_BENEFICIARIES,
+                    ErrorCode::InvalidBeneficiaryCount
+                );
+
+                let total_shares: u16 = beneficiaries.iter().map(|b| b.share_bps).sum();
+                require!(
+                    total_shares == BASIS_POINTS_TOTAL,
+                    ErrorCode::InvalidShareDistribution
+                );
+
+                // Fewer beneficiaries must not leave the cancel freeze quorum out of reach
+                require!(
+                    self.freeze_quorum as usize <= self.guardians.len() + beneficiaries.len(),
+                    ErrorCode::InvalidCancelTimelock
+                );
+            }
+            ConfigChange::Timeout { timeout_seconds } => {
+                require!(*timeout_seconds > 0, ErrorCode::InvalidTimeout);
+                require!(
+                    self.min_heartbeat_interval_seconds < *timeout_seconds,
+                    ErrorCode::InvalidHeartbeatInterval
+                );
+            }
+            ConfigChange::Fallback {
+                claim_deadline_seconds,
+                ..
+            } => {
+                require!(*claim_deadline_seconds > 0, ErrorCode::InvalidClaimDeadline);
+            }
+            ConfigChange::HeartbeatSchedule { schedule } => {
+                let valid = match *schedule {
+                    HeartbeatSchedule::Sliding => true,
+                    HeartbeatSchedule::FixedPeriod { months } => {
+                        months > 0 && months <= MAX_SCHEDULE_MONTHS
+                    }
+                    HeartbeatSchedule::AlignedInterval {
+                        interval_seconds,
+                        offset_seconds,
+                    } => interval_seconds > 0 && (0..interval_seconds).contains(&offset_seconds),
+                };
+                require!(valid, ErrorCode::InvalidHeartbeatSchedule);
+            }
+            ConfigChange::ChallengePeriod {
+                challenge_period_seconds,
+            } => {
+                // Bounded so a stolen key cannot hold distribution off indefinitely
+                require!(
+                    (0..=MAX_CHALLENGE_PERIOD_SECONDS).contains(challenge_period_seconds),
+                    ErrorCode::InvalidChallengePeriod
+                );
+            }
+            ConfigChange::Executor {
+                max_share_adjustment_bps,
+                executor_window_seconds,
+                expenses,
+                ..
+            } => {
+                require!(
+                    *max_share_adjustment_bps <= BASIS_POINTS_TOTAL
+                        && (0..=MAX_EXECUTOR_WINDOW_SECONDS).contains(executor_window_seconds)
+                        && expenses.len() <= MAX_APPROVED_EXPENSES,
+                    ErrorCode::InvalidExecutorConfig
+                );
+
+                // Expense payees must be unique with a positive cap
+                for (i, expense) in expenses.iter().enumerate() {
+                    require!(
+                        expense.cap_lamports > 0
+                            && !expenses[..i].iter().any(|e| e.payee == expense.payee),
+                        ErrorCode::InvalidExecutorConfig
+                    );
+                }
+            }
+            ConfigChange::ExpiryActions { .. } => {}
+            ConfigChange::WithdrawalLimit { limit } => {
+                if let Some(limit) = limit {
+                    require!(limit.period_seconds > 0, ErrorCode::InvalidWithdrawalLimit);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delay before an owner config change applies. A cancel timelock holds config
+    /// changes back just as long, since a new heir, executor or expiry action can move
+    /// the escrow as surely as a cancel.
+    pub fn config_delay(&self) -> i64 {
+        self.config_delay_seconds.max(self.cancel_delay_seconds)
+    }
+
+    /// Seconds `change` must wait before it can be applied. Tightening the withdrawal
+    /// limit applies at once, loosening it waits out at least one full current period.
+    pub fn config_change_delay(&self, change: &ConfigChange) -> i64 {
+        let ConfigChange::WithdrawalLimit { limit } = change else {
+            return self.config_delay();
+        };
+        let Some(current) = &self.withdrawal_limit else {
+            return 0;
+        };
+
+        let loosens = match limit {
+            None => true,
+            Some(limit) => {
+                limit.lamports_per_period > current.lamports_per_period
+                    || limit.tokens_per_period > current.tokens_per_period
+                    || limit.period_seconds < current.period_seconds
+            }
+        };
+
+        if loosens {
+            self.config_delay().max(current.period_seconds)
+        } else {
+            0
+        }
+    }
+
+    /// With a cancel timelock, owner actions that take assets out of the escrow need a
+    /// cancel request that has waited out the delay without being frozen
+    pub fn require_cancel_unlocked(&self, now: i64) -> Result<()> {
+        if self.cancel_delay_seconds == 0 {
+            return Ok(());
+        }
+
+        require!(
+            self.cancel_requested_at > 0,
+            ErrorCode::CancelNotRequested
+        );
+        require!(!self.cancel_frozen, ErrorCode::CancelFrozen);
+        require!(
+            now >= self
+                .cancel_requested_at
+                .saturating_add(self.cancel_delay_seconds),
+            ErrorCode::CancelTimelockActive
+        );
+
+        Ok(())
+    }
+
+    /// Check that `beneficiary_index` is next in the executor's distribution order, if one
+    /// is set, and move past it. With `repeat`, the beneficiary paid last may be paid again.
+    pub fn take_distribution_turn(&mut self, beneficiary_index: usize, repeat: bool) -> Result<()> {
+        if self.distribution_order.is_empty() {
+            return Ok(());
+        }
+
+        let made = self.distributions_made as usize;
+        if repeat && made > 0 && self.distribution_order[made - 1] as usize == beneficiary_index {
+            return Ok(());
+        }
+
+        let next = self
+            .distribution_order
+            .get(made)
+            .ok_or(ErrorCode::DistributionOutOfOrder)?;
+        require!(
+            *next as usize == beneficiary_index,
+            ErrorCode::DistributionOutOfOrder
+        );
+        self.distributions_made += 1;
+
+        Ok(())
+    }
+
+    /// Number of distinct owners signing: the primary owner plus any co-owners
+    /// passed as signers in `remaining_accounts`
+    pub fn owner_signatures(&self, remaining_accounts: &[AccountInfo]) -> usize {
+        let mut signers = vec![self.owner];
+        for account in remaining_accounts {
+            if account.is_signer
+                && self.co_owners.iter().any(|c| c.key == *account.key)
+                && !signers.contains(account.key)
+            {
+                signers.push(*account.key);
+            }
+        }
+        signers.len()
+    }
+}
+
+// Per-owner counter handing out switch IDs "#0", "#1", ... so clients can
+// enumerate an owner's indexed switches without scanning
+#[account]
+#[derive(InitSpace)]
+pub struct Registry {
+    pub owner: Pubkey,                              // 32
+    pub switch_count: u64,                          // 8
+    pub bump: u8,                                   // 1
 }
 
-#[derive(Accounts)]
-#[instruction(switch_id: String)]
-pub struct WithdrawSol<'info> {
-    #[account(
-        seeds = [b"switch", owner.key().as_ref(), switch_id.as_bytes()],
-        bump = switch.bump,
-        has_one = owner
-    )]
-    pub switch: Account<'info, Switch>,
-    
-    #[account(
-        mut,
-        seeds = [b"escrow", owner.key().as_ref(), switch_id.as_bytes()],
-        bump
-    )]
-    /// CHECK: PDA escrow account
-    pub escrow: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+impl Registry {
+    /// Switch ID the next indexed switch is seeded with
+    pub fn next_switch_id(&self) -> String {
+        format!("{}{}", INDEXED_SWITCH_ID_PREFIX, self.switch_count)
+    }
 }
 
-// ============================================================================
-// State
-// ============================================================================
+// Reverse index of the switches naming a beneficiary, so heirs can discover
+// their inheritances without scanning every switch
+#[account]
+pub struct BeneficiaryIndex {
+    pub beneficiary: Pubkey,                        // 32
+    pub switches: Vec<Pubkey>,                      // 4 + (32 * n), grows per switch
+    pub bump: u8,                                   // 1
+}
+
+impl BeneficiaryIndex {
+    /// Account size needed to hold `switch_count` switch keys
+    pub fn space(switch_count: usize) -> usize {
+        8 + 32 + 4 + 32 * switch_count + 1
+    }
+}
 
+// Last HEARTBEAT_HISTORY_LEN heartbeats of a switch, kept as evidence for disputes
 #[account]
 #[derive(InitSpace)]
-pub struct Switch {
+pub struct HeartbeatHistory {
+    pub switch: Pubkey,                             // 32
+    #[max_len(HEARTBEAT_HISTORY_LEN)]
+    pub records: Vec<HeartbeatRecord>,              // 4 + (16 * 49) = 788
+    pub next_index: u8,                             // 1, slot overwritten once full
+    pub bump: u8,                                   // 1
+}
+
+impl HeartbeatHistory {
+    /// Record a heartbeat, overwriting the oldest record once the buffer is full
+    pub fn append(&mut self, signer: Pubkey, method: HeartbeatMethod, clock: &Clock) {
+        let record = HeartbeatRecord {
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+            signer,
+            method,
+        };
+
+        let next = self.next_index as usize;
+        if next < self.records.len() {
+            self.records[next] = record;
+        } else {
+            self.records.push(record);
+        }
+        self.next_index = ((next + 1) % HEARTBEAT_HISTORY_LEN) as u8;
+    }
+}
+
+// Per-owner heartbeat shared by every linked switch
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
     pub owner: Pubkey,                              // 32
-    #[max_len(MAX_SWITCH_ID_LEN)]
-    pub switch_id: String,                          // 4 + 32 = 36
-    #[max_len(MAX_BENEFICIARIES)]
-    pub beneficiaries: Vec<Beneficiary>,            // 4 + (10 * 34) = 344
-    pub token_type: TokenType,                      // 1 + 32 = 33
     pub timeout_seconds: i64,                       // 8
     pub heartbeat_deadline: i64,                    // 8
-    pub status: SwitchStatus,                       // 1
     pub bump: u8,                                   // 1
+    pub linked_switches: u32,                       // 4, live switches linked to the profile
+}
+
+// Companion account holding instructions to run on expiry
+#[account]
+#[derive(InitSpace)]
+pub struct ExpiryActions {
+    pub switch: Pubkey,                             // 32
+    #[max_len(MAX_EXPIRY_ACTIONS)]
+    pub actions: Vec<ExpiryAction>,                 // 4 + (4 * 569) = 2280
+    pub bump: u8,                                   // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ExpiryAction {
+    pub program_id: Pubkey,                         // 32
+    #[max_len(MAX_ACTION_ACCOUNTS)]
+    pub accounts: Vec<ActionAccountMeta>,           // 4 + (8 * 34) = 276
+    #[max_len(MAX_ACTION_DATA_LEN)]
+    pub data: Vec<u8>,                              // 4 + 256 = 260
+    pub executed: bool,                             // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HeartbeatRecord {
+    pub timestamp: i64,
+    pub slot: u64,
+    pub signer: Pubkey,                     // Owner, co-owner or delegate key, passkey hash or padded Ethereum address
+    pub method: HeartbeatMethod,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum HeartbeatMethod {
+    Direct,                     // Transaction signed by the heartbeat key
+    SignedMessage,              // Off-chain signature verified on-chain
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ActionAccountMeta {
+    pub pubkey: Pubkey,         // 32
+    pub is_signer: bool,        // 1 (only the escrow may sign)
+    pub is_writable: bool,      // 1
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -624,6 +5276,131 @@ pub struct Beneficiary {
     pub share_bps: u16,         // 2 (basis points, e.g., 5000 = 50%)
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct StakeInheritance {
+    pub stake_account: Pubkey,  // 32 (staker and withdrawer held by the escrow)
+    pub beneficiary: Pubkey,    // 32
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HeldAuthority {
+    pub kind: AuthorityKind,    // 1
+    pub target: Pubkey,         // 32 (mint, or program id for upgrade authorities)
+    pub beneficiary: Pubkey,    // 32
+}
+
+impl HeldAuthority {
+    /// Account the authority is set on: the mint, or the program's data account
+    pub fn target_account(&self) -> Pubkey {
+        match self.kind {
+            AuthorityKind::ProgramUpgrade => {
+                bpf_loader_upgradeable::get_program_data_address(&self.target)
+            }
+            AuthorityKind::MintTokens | AuthorityKind::FreezeAccount => self.target,
+        }
+    }
+}
+
+// Returned by `get_status` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwitchStatusView {
+    pub status: SwitchStatus,                   // Effective status, Expired once the deadline passes
+    pub heartbeat_deadline: i64,                // Effective deadline
+    pub seconds_remaining: i64,                 // 0 once the deadline has passed
+    pub token_type: TokenType,
+    pub entitlements: Vec<BeneficiaryEntitlement>,
+    pub last_heartbeat_at: i64,                 // Primary owner's last heartbeat
+    pub heartbeat_count: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BeneficiaryEntitlement {
+    pub address: Pubkey,
+    pub share_bps: u16,
+    pub amount: Option<u64>,                    // Lamports or token amount at current escrow balance, None if unknown
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CoOwner {
+    pub key: Pubkey,            // 32
+    pub last_heartbeat_at: i64, // 8
+}
+
+// Per-period cap on `withdraw_partial`, tokens counted in raw units across mints
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WithdrawalLimit {
+    pub lamports_per_period: u64,   // 8
+    pub tokens_per_period: u64,     // 8
+    pub period_seconds: i64,        // 8
+}
+
+// Published statement of a warrant canary switch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Canary {
+    pub statement_hash: [u8; 32],   // 32
+    #[max_len(MAX_CANARY_URI_LEN)]
+    pub uri: String,                // 4 + 128 = 132
+    pub published_at: i64,          // 8
+    pub lapsed_at: i64,             // 8, 0 unless the canary lapsed
+}
+
+// Owner change staged behind the config delay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,       // 1 + 344 = 345
+    pub proposed_at: i64,           // 8
+    pub activates_at: i64,          // 8
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum ConfigChange {
+    Beneficiaries {
+        #[max_len(MAX_BENEFICIARIES)]
+        beneficiaries: Vec<Beneficiary>,
+    },
+    Timeout {
+        timeout_seconds: i64,
+    },
+    Fallback {
+        fallback: Pubkey,
+        claim_deadline_seconds: i64,
+    },
+    HeartbeatSchedule {
+        schedule: HeartbeatSchedule,
+    },
+    ChallengePeriod {
+        challenge_period_seconds: i64,
+    },
+    Executor {
+        executor: Option<Pubkey>,
+        max_share_adjustment_bps: u16,
+        executor_window_seconds: i64,
+        #[max_len(MAX_APPROVED_EXPENSES)]
+        expenses: Vec<ApprovedExpense>,
+    },
+    ExpiryActions {
+        actions_hash: [u8; 32],     // sha256 of the borsh-encoded actions
+    },
+    WithdrawalLimit {
+        limit: Option<WithdrawalLimit>,
+    },
+}
+
+// Payee the executor may pay from the escrow, up to the owner-set cap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ApprovedExpense {
+    pub payee: Pubkey,          // 32
+    pub cap_lamports: u64,      // 8
+    pub paid_lamports: u64,     // 8
+}
+
+// Last liveness challenge raised by a beneficiary, for rate limiting
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LivenessChallenge {
+    pub beneficiary: Pubkey,    // 32
+    pub challenged_at: i64,     // 8
+}
+
 // Enhanced beneficiary with specific asset allocations
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct BeneficiaryAllocation {
@@ -650,11 +5427,140 @@ pub enum AssetType {
     SplToken { mint: Pubkey },
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AuthorityKind {
+    ProgramUpgrade,
+    MintTokens,
+    FreezeAccount,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ExpiryPolicy {
+    Any,                        // Expires when any owner misses a heartbeat
+    All,                        // Expires only when every owner has missed
+    Threshold { missed: u8 },   // Expires when this many owners have missed
+}
+
+// When a heartbeat is due. Windowed schedules are in UTC and expect one heartbeat
+// per window: a heartbeat anywhere in a window is due again by the end of the
+// next window, so early or repeated heartbeats never extend the deadline further.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum HeartbeatSchedule {
+    Sliding,                                            // Deadline is heartbeat + timeout
+    FixedPeriod { months: u8 },                         // Calendar months, e.g. 1 = monthly, 3 = quarterly
+    AlignedInterval { interval_seconds: i64, offset_seconds: i64 }, // Windows start at offset + k * interval
+}
+
+impl HeartbeatSchedule {
+    /// Deadline earned by a heartbeat at `heartbeat_at`
+    pub fn deadline_after(&self, heartbeat_at: i64, timeout_seconds: i64) -> i64 {
+        match *self {
+            HeartbeatSchedule::Sliding => heartbeat_at.saturating_add(timeout_seconds),
+            HeartbeatSchedule::FixedPeriod { months } => {
+                let months = months as i64;
+                let period = month_index(heartbeat_at).div_euclid(months);
+                month_start((period + 2) * months)
+            }
+            HeartbeatSchedule::AlignedInterval {
+                interval_seconds,
+                offset_seconds,
+            } => {
+                let window = (heartbeat_at - offset_seconds).div_euclid(interval_seconds);
+                offset_seconds.saturating_add((window + 2).saturating_mul(interval_seconds))
+            }
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum SwitchStatus {
     Active,
     Expired,
     Canceled,
+    Duress,     // Heartbeats and expiry as usual, owner actions frozen until guardians clear it
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct BatchHeartbeatResult {
+    pub switch: Pubkey,
+    pub switch_id: String,
+    pub success: bool,
+    pub heartbeat_deadline: i64,
+}
+
+#[event]
+pub struct LivenessChallenged {
+    pub switch: Pubkey,
+    pub beneficiary: Pubkey,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct CancelRequested {
+    pub switch: Pubkey,
+    pub requested_at: i64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct CancelFrozen {
+    pub switch: Pubkey,
+    pub votes: Vec<Pubkey>,
+}
+
+#[event]
+pub struct CancelThawed {
+    pub switch: Pubkey,
+    pub votes: Vec<Pubkey>,
+}
+
+#[event]
+pub struct CanaryPublished {
+    pub switch: Pubkey,
+    pub statement_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[event]
+pub struct CanaryLapsed {
+    pub switch: Pubkey,
+    pub statement_hash: [u8; 32],
+    pub lapsed_at: i64,
+}
+
+#[event]
+pub struct DuressSignaled {
+    pub switch: Pubkey,
+    pub signaled_at: i64,
+}
+
+#[event]
+pub struct DuressCleared {
+    pub switch: Pubkey,
+    pub votes: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ConfigChangeProposed {
+    pub switch: Pubkey,
+    pub change: ConfigChange,
+    pub activates_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeApplied {
+    pub switch: Pubkey,
+    pub change: ConfigChange,
+}
+
+#[event]
+pub struct ConfigChangeCanceled {
+    pub switch: Pubkey,
+    pub change: ConfigChange,
 }
 
 // ============================================================================
@@ -663,7 +5569,7 @@ pub enum SwitchStatus {
 
 #[error_code]
 pub enum ErrorCode {
-    #[msg("Invalid switch ID (1-32 characters allowed)")]
+    #[msg("Invalid switch ID (1-32 characters, not starting with '#')")]
     InvalidSwitchId,
     
     #[msg("Invalid number of beneficiaries (1-10 allowed)")]
@@ -701,4 +5607,262 @@ pub enum ErrorCode {
     
     #[msg("Invalid asset allocation - beneficiary must have at least one asset")]
     InvalidAssetAllocation,
+    
+    #[msg("Claim deadline must be positive")]
+    InvalidClaimDeadline,
+    
+    #[msg("No fallback address has been set")]
+    FallbackNotSet,
+    
+    #[msg("Account does not match the switch fallback")]
+    InvalidFallback,
+    
+    #[msg("Claim deadline has not passed yet")]
+    ClaimDeadlineNotPassed,
+    
+    #[msg("Invalid escrow or fallback token account")]
+    InvalidEscrowTokenAccount,
+    
+    #[msg("Challenge period must be between 0 and 90 days")]
+    InvalidChallengePeriod,
+    
+    #[msg("Distribution is blocked during the challenge period")]
+    ChallengePeriodActive,
+    
+    #[msg("Challenge period has ended - switch can no longer be revived")]
+    ChallengePeriodEnded,
+    
+    #[msg("Invalid co-owners (1-5 unique co-owners, set once)")]
+    InvalidCoOwners,
+    
+    #[msg("Expiry threshold must be between 1 and the number of owners")]
+    InvalidExpiryPolicy,
+    
+    #[msg("Owner quorum must be between 1 and the number of owners")]
+    InvalidOwnerQuorum,
+    
+    #[msg("Signer is not a co-owner of this switch")]
+    NotCoOwner,
+    
+    #[msg("Not enough owner signatures")]
+    OwnerQuorumNotMet,
+    
+    #[msg("Stake account is already registered on this switch")]
+    StakeAccountAlreadyRegistered,
+    
+    #[msg("Too many stake accounts (max 5)")]
+    TooManyStakeAccounts,
+    
+    #[msg("Stake account is not registered on this switch")]
+    StakeAccountNotFound,
+    
+    #[msg("Registered stake accounts must be reclaimed or released first")]
+    StakeAccountsRegistered,
+    
+    #[msg("Target account does not match the authority target")]
+    InvalidAuthorityTarget,
+    
+    #[msg("Authority is already held by this switch")]
+    AuthorityAlreadyHeld,
+    
+    #[msg("Too many held authorities (max 5)")]
+    TooManyAuthorities,
+    
+    #[msg("Authority is not held by this switch")]
+    AuthorityNotFound,
+    
+    #[msg("Held authorities must be reclaimed or released first")]
+    AuthoritiesHeld,
+    
+    #[msg("Invalid expiry action (1-4 actions, max 8 accounts and 256 bytes of data)")]
+    InvalidExpiryAction,
+    
+    #[msg("Only the escrow may sign expiry actions")]
+    InvalidActionSigner,
+    
+    #[msg("Expiry action has already been executed")]
+    ExpiryActionAlreadyExecuted,
+    
+    #[msg("Profile has expired - heartbeat rejected")]
+    ProfileExpired,
+    
+    #[msg("Switch is linked to a profile - send the heartbeat to the profile")]
+    SwitchLinkedToProfile,
+    
+    #[msg("Switch is linked to a profile that was not provided")]
+    ProfileRequired,
+    
+    #[msg("Profile does not match the one linked to the switch")]
+    ProfileMismatch,
+    
+    #[msg("Account is not a valid switch PDA")]
+    InvalidSwitchAccount,
+    
+    #[msg("Signer does not own this switch")]
+    NotSwitchOwner,
+    
+    #[msg("Label is too long (max 64 characters)")]
+    InvalidLabel,
+    
+    #[msg("Beneficiary index account missing from remaining accounts")]
+    BeneficiaryIndexMissing,
+    
+    #[msg("Invalid heartbeat schedule (1-12 months, or a positive interval with an offset inside it)")]
+    InvalidHeartbeatSchedule,
+    
+    #[msg("Minimum heartbeat interval must be non-negative and shorter than the timeout")]
+    InvalidHeartbeatInterval,
+    
+    #[msg("Heartbeat sent before the minimum interval has elapsed")]
+    HeartbeatTooSoon,
+    
+    #[msg("Heartbeat history account does not belong to the switch")]
+    InvalidHeartbeatHistory,
+    
+    #[msg("A liveness challenge is already waiting for the owner")]
+    LivenessChallengePending,
+    
+    #[msg("Beneficiary challenged liveness too recently")]
+    LivenessChallengeCooldown,
+    
+    #[msg("Invalid executor configuration")]
+    InvalidExecutorConfig,
+    
+    #[msg("Signer is not the switch executor")]
+    NotExecutor,
+    
+    #[msg("Executor window is closed")]
+    ExecutorWindowClosed,
+    
+    #[msg("Distribution is held until the executor window closes")]
+    ExecutorWindowOpen,
+    
+    #[msg("Share adjustments must cover every beneficiary, net to zero and stay within bounds")]
+    InvalidShareAdjustment,
+    
+    #[msg("Payee is not an approved expense")]
+    ExpenseNotApproved,
+    
+    #[msg("Expense payment exceeds its cap")]
+    ExpenseCapExceeded,
+    
+    #[msg("Distribution order must list every beneficiary exactly once")]
+    InvalidDistributionOrder,
+    
+    #[msg("Beneficiary is not next in the executor's distribution order")]
+    DistributionOutOfOrder,
+    
+    #[msg("Withdrawal period must be positive")]
+    InvalidWithdrawalLimit,
+    
+    #[msg("Withdrawal exceeds the limit for this period")]
+    WithdrawalLimitExceeded,
+    
+    #[msg("Invalid cancel timelock (positive delay, up to 5 unique guardians, reachable freeze quorum)")]
+    InvalidCancelTimelock,
+    
+    #[msg("Cancel timelock can only be lengthened once set")]
+    CancelTimelockLocked,
+    
+    #[msg("A cancel is already pending")]
+    CancelAlreadyRequested,
+    
+    #[msg("No cancel has been requested")]
+    CancelNotRequested,
+    
+    #[msg("Cancel timelock has not elapsed yet")]
+    CancelTimelockActive,
+    
+    #[msg("Pending cancel was frozen by guardians and beneficiaries")]
+    CancelFrozen,
+    
+    #[msg("Signer is neither a guardian nor a beneficiary")]
+    NotGuardianOrBeneficiary,
+    
+    #[msg("Voter has already voted to freeze this cancel")]
+    FreezeAlreadyVoted,
+    
+    #[msg("Config change delay cannot be negative")]
+    InvalidConfigDelay,
+    
+    #[msg("Config change delay can only be lengthened")]
+    ConfigDelayLocked,
+    
+    #[msg("A config change is already pending")]
+    ConfigChangePending,
+    
+    #[msg("No config change is pending")]
+    NoPendingConfigChange,
+    
+    #[msg("Config change delay has not elapsed yet")]
+    ConfigChangeDelayActive,
+    
+    #[msg("Switch uses a heartbeat hash chain - reveal the next preimage")]
+    HeartbeatPreimageRequired,
+    
+    #[msg("Preimage does not hash to the committed chain value")]
+    InvalidHeartbeatPreimage,
+    
+    #[msg("Heartbeat chain is already set - re-seed it with a preimage")]
+    HeartbeatChainAlreadySet,
+    
+    #[msg("Switch does not use a heartbeat hash chain")]
+    HeartbeatChainNotSet,
+    
+    #[msg("A duress code needs guardians to clear the duress state")]
+    DuressRequiresGuardians,
+    
+    #[msg("Switch is not under duress")]
+    SwitchNotInDuress,
+    
+    #[msg("Signer is not a guardian of this switch")]
+    NotGuardian,
+    
+    #[msg("Guardian has already voted to clear this duress")]
+    DuressClearAlreadyVoted,
+    
+    #[msg("Canary URI is too long (max 128 characters)")]
+    InvalidCanaryUri,
+    
+    #[msg("Switch is not a warrant canary")]
+    NotCanary,
+    
+    #[msg("Not available for warrant canaries")]
+    NotForCanary,
+    
+    #[msg("Canary has lapsed and cannot be revived")]
+    CanaryLapsed,
+    
+    #[msg("No passkey is registered on this switch")]
+    PasskeyNotSet,
+    
+    #[msg("Signature verification instruction must immediately precede this one")]
+    MissingSignatureVerification,
+    
+    #[msg("Signature verification does not cover the expected key and message")]
+    InvalidSignatureVerification,
+    
+    #[msg("Passkey assertion does not match the switch challenge")]
+    InvalidPasskeyAssertion,
+    
+    #[msg("No Ethereum address is registered on this switch")]
+    EthAddressNotSet,
+    
+    #[msg("Signed message is not the expected heartbeat for this switch and nonce")]
+    InvalidEthHeartbeatMessage,
+    
+    #[msg("Voter has no freeze vote to withdraw")]
+    FreezeVoteNotFound,
+    
+    #[msg("Expiry actions do not match a hash approved through a config change")]
+    ExpiryActionsNotApproved,
+    
+    #[msg("Profile heartbeat must include every live linked switch exactly once")]
+    LinkedSwitchesRequired,
+    
+    #[msg("Sweeping an SPL switch must include the escrow's token account for its mint")]
+    SweepTokenAccountRequired,
+    
+    #[msg("Joint switches cannot use a heartbeat chain")]
+    JointSwitchNotChainable,
 }
@@ -0,0 +1,261 @@
+//! Example of a program-owned PDA (e.g. a DAO treasury) owning a dead man's switch.
+//! Every switch instruction is invoked through CPI with the PDA signing, while a
+//! separate wallet pays rent and funds the escrow.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer as SystemTransfer};
+use deadmansswitch::cpi::accounts::{CancelSwitch, GetStatus, InitializeSwitch, SendHeartbeat};
+use deadmansswitch::program::Deadmansswitch;
+use deadmansswitch::{Beneficiary, SwitchStatus, TokenType};
+
+declare_id!("FxXQNeevtyq9wd5Ezm9Md3wRaUq1L7jbn4nz3seAW7sC");
+
+const AUTHORITY_SEED: &[u8] = b"authority";
+
+#[program]
+pub mod pda_owner {
+    use super::*;
+
+    /// Create a switch owned by the authority PDA, with `payer` covering rent
+    /// for the switch and the beneficiary's index
+    pub fn create_switch(
+        ctx: Context<CreateSwitch>,
+        switch_id: String,
+        timeout_seconds: i64,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let bump = ctx.bumps.authority;
+        let seeds = &[AUTHORITY_SEED, &[bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.deadmansswitch_program.to_account_info(),
+            InitializeSwitch {
+                switch: ctx.accounts.switch.to_account_info(),
+                history: ctx.accounts.history.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                owner: ctx.accounts.authority.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(vec![ctx.accounts.beneficiary_index.to_account_info()]);
+
+        deadmansswitch::cpi::initialize_switch(
+            cpi_ctx,
+            switch_id,
+            timeout_seconds,
+            vec![Beneficiary {
+                address: beneficiary,
+                share_bps: 10000,
+            }],
+            TokenType::Sol,
+        )
+    }
+
+    /// Fund the switch escrow from the payer
+    pub fn fund_switch(ctx: Context<FundSwitch>, _switch_id: String, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+
+        transfer(cpi_ctx, amount)
+    }
+
+    /// Send a heartbeat on behalf of the authority PDA
+    pub fn heartbeat(ctx: Context<Heartbeat>, switch_id: String) -> Result<()> {
+        let bump = ctx.bumps.authority;
+        let seeds = &[AUTHORITY_SEED, &[bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.deadmansswitch_program.to_account_info(),
+            SendHeartbeat {
+                switch: ctx.accounts.switch.to_account_info(),
+                history: ctx.accounts.history.to_account_info(),
+                owner: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        deadmansswitch::cpi::send_heartbeat(cpi_ctx, switch_id)
+    }
+
+    /// Fail unless the switch is still alive, reading its status through CPI
+    pub fn assert_alive(ctx: Context<AssertAlive>) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.deadmansswitch_program.to_account_info(),
+            GetStatus {
+                switch: ctx.accounts.switch.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                escrow_token_account: None,
+                profile: None,
+            },
+        );
+
+        let status = deadmansswitch::cpi::get_status(cpi_ctx)?.get();
+
+        require!(
+            matches!(status.status, SwitchStatus::Active | SwitchStatus::Duress),
+            PdaOwnerError::SwitchNotAlive
+        );
+
+        msg!("Switch alive, {} seconds remaining", status.seconds_remaining);
+
+        Ok(())
+    }
+
+    /// Cancel the switch on behalf of the authority PDA
+    pub fn cancel(ctx: Context<Cancel>, switch_id: String) -> Result<()> {
+        let bump = ctx.bumps.authority;
+        let seeds = &[AUTHORITY_SEED, &[bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.deadmansswitch_program.to_account_info(),
+            CancelSwitch {
+                switch: ctx.accounts.switch.to_account_info(),
+                owner: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(vec![ctx.accounts.beneficiary_index.to_account_info()]);
+
+        deadmansswitch::cpi::cancel_switch(cpi_ctx, switch_id)
+    }
+}
+
+// ============================================================================
+// Account Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(switch_id: String, timeout_seconds: i64, beneficiary: Pubkey)]
+pub struct CreateSwitch<'info> {
+    /// CHECK: Program-owned authority PDA that owns the switch
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Initialized by the deadmansswitch program
+    #[account(
+        mut,
+        seeds = [b"switch", authority.key().as_ref(), switch_id.as_bytes()],
+        bump,
+        seeds::program = deadmansswitch_program.key()
+    )]
+    pub switch: UncheckedAccount<'info>,
+    
+    /// CHECK: Heartbeat history PDA, initialized by the deadmansswitch program
+    #[account(
+        mut,
+        seeds = [b"history", switch.key().as_ref()],
+        bump,
+        seeds::program = deadmansswitch_program.key()
+    )]
+    pub history: UncheckedAccount<'info>,
+    
+    /// CHECK: Escrow PDA of the deadmansswitch program
+    #[account(
+        seeds = [b"escrow", authority.key().as_ref(), switch_id.as_bytes()],
+        bump,
+        seeds::program = deadmansswitch_program.key()
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    
+    /// CHECK: Beneficiary index PDA, created or extended by the deadmansswitch program
+    #[account(
+        mut,
+        seeds = [b"beneficiary_index", beneficiary.as_ref()],
+        bump,
+        seeds::program = deadmansswitch_program.key()
+    )]
+    pub beneficiary_index: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub deadmansswitch_program: Program<'info, Deadmansswitch>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(switch_id: String)]
+pub struct FundSwitch<'info> {
+    /// CHECK: Program-owned authority PDA that owns the switch
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Escrow PDA of the deadmansswitch program
+    #[account(
+        mut,
+        seeds = [b"escrow", authority.key().as_ref(), switch_id.as_bytes()],
+        bump,
+        seeds::program = deadmansswitch::ID
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    /// CHECK: Program-owned authority PDA that owns the switch
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the deadmansswitch program
+    #[account(mut)]
+    pub switch: UncheckedAccount<'info>,
+    
+    /// CHECK: Heartbeat history PDA, validated by the deadmansswitch program
+    #[account(mut)]
+    pub history: UncheckedAccount<'info>,
+    
+    pub deadmansswitch_program: Program<'info, Deadmansswitch>,
+}
+
+#[derive(Accounts)]
+pub struct AssertAlive<'info> {
+    /// CHECK: Validated by the deadmansswitch program
+    pub switch: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the deadmansswitch program
+    pub escrow: UncheckedAccount<'info>,
+    
+    pub deadmansswitch_program: Program<'info, Deadmansswitch>,
+}
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    /// CHECK: Program-owned authority PDA that owns the switch
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the deadmansswitch program
+    #[account(mut)]
+    pub switch: UncheckedAccount<'info>,
+    
+    /// CHECK: Beneficiary index PDA, validated by the deadmansswitch program
+    #[account(mut)]
+    pub beneficiary_index: UncheckedAccount<'info>,
+    
+    pub deadmansswitch_program: Program<'info, Deadmansswitch>,
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[error_code]
+pub enum PdaOwnerError {
+    #[msg("Switch is no longer alive")]
+    SwitchNotAlive,
+}
@@ -1,8 +1,23 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Deadmansswitch } from "../target/types/deadmansswitch";
-import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  Secp256k1Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  createMint,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
 import { expect } from "chai";
+import { createECDH, createHash, generateKeyPairSync, randomBytes, sign } from "crypto";
 
 describe("deadmansswitch", () => {
   const provider = anchor.AnchorProvider.env();
@@ -19,6 +34,17 @@ describe("deadmansswitch", () => {
   let switchBump: number;
   let escrowBump: number;
 
+  // Every instruction that creates or closes a switch keeps the beneficiaries'
+  // reverse index PDAs in sync, so pass both test beneficiaries' indexes along
+  const beneficiaryIndexes = [beneficiary1, beneficiary2].map(beneficiary => ({
+    pubkey: PublicKey.findProgramAddressSync(
+      [Buffer.from("beneficiary_index"), beneficiary.publicKey.toBuffer()],
+      program.programId
+    )[0],
+    isSigner: false,
+    isWritable: true,
+  }));
+
   before(async () => {
     // Derive PDAs
     [switchPda, switchBump] = PublicKey.findProgramAddressSync(
@@ -69,7 +95,9 @@ describe("deadmansswitch", () => {
           switch: switchPda,
           escrow: escrowPda,
           owner: owner.publicKey,
+          payer: owner.publicKey,
         })
+        .remainingAccounts(beneficiaryIndexes)
         .rpc();
 
       console.log("Switch initialized:", tx);
@@ -129,7 +157,9 @@ describe("deadmansswitch", () => {
             switch: anotherSwitch,
             escrow: anotherEscrow,
             owner: anotherOwner.publicKey,
+            payer: anotherOwner.publicKey,
           })
+          .remainingAccounts(beneficiaryIndexes)
           .signers([anotherOwner])
           .rpc();
         
@@ -220,6 +250,7 @@ describe("deadmansswitch", () => {
         .triggerExpiry()
         .accounts({
           switch: switchPda,
+          profile: null,
         })
         .rpc();
 
@@ -316,7 +347,9 @@ describe("deadmansswitch", () => {
           switch: newSwitch,
           escrow: newEscrow,
           owner: newOwner.publicKey,
+          payer: newOwner.publicKey,
         })
+        .remainingAccounts(beneficiaryIndexes)
         .signers([newOwner])
         .rpc();
 
@@ -327,6 +360,7 @@ describe("deadmansswitch", () => {
           switch: newSwitch,
           owner: newOwner.publicKey,
         })
+        .remainingAccounts(beneficiaryIndexes)
         .signers([newOwner])
         .rpc();
 
@@ -335,4 +369,3641 @@ describe("deadmansswitch", () => {
       expect(switchAccount.status).to.have.property("canceled");
     });
   });
+
+  describe("sweep_unclaimed", () => {
+    it("Sweeps unclaimed escrow to the fallback after the claim deadline", async () => {
+      const sweepOwner = Keypair.generate();
+      const fallback = Keypair.generate();
+      const switchId = "sweep";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        sweepOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [sweepSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), sweepOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [sweepEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), sweepOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: sweepSwitch,
+          escrow: sweepEscrow,
+          owner: sweepOwner.publicKey,
+          payer: sweepOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([sweepOwner])
+        .rpc();
+
+      await program.methods
+        .setFallback(switchId, fallback.publicKey, new BN(1))
+        .accounts({
+          switch: sweepSwitch,
+          owner: sweepOwner.publicKey,
+        })
+        .signers([sweepOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: sweepOwner.publicKey,
+            toPubkey: sweepEscrow,
+            lamports: 0.2 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [sweepOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: sweepSwitch, profile: null })
+        .rpc();
+
+      // Sweeping inside the claim window must fail
+      try {
+        await program.methods
+          .sweepUnclaimed()
+          .accounts({
+            switch: sweepSwitch,
+            escrow: sweepEscrow,
+            fallback: fallback.publicKey,
+            owner: sweepOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .rpc();
+        expect.fail("Should have failed - claim deadline has not passed");
+      } catch (error: any) {
+        expect(error.message).to.include("ClaimDeadlineNotPassed");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .sweepUnclaimed()
+        .accounts({
+          switch: sweepSwitch,
+          escrow: sweepEscrow,
+          fallback: fallback.publicKey,
+          owner: sweepOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .rpc();
+
+      const fallbackBalance = await provider.connection.getBalance(fallback.publicKey);
+      expect(fallbackBalance).to.be.at.least(0.2 * LAMPORTS_PER_SOL);
+      expect(await provider.connection.getAccountInfo(sweepEscrow)).to.be.null;
+      expect(await provider.connection.getAccountInfo(sweepSwitch)).to.be.null;
+    });
+
+    it("Sweeps an SPL escrow only together with its token account", async () => {
+      const sweepOwner = Keypair.generate();
+      const fallback = Keypair.generate();
+      const switchId = "sweep-spl";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        sweepOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [sweepSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), sweepOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [sweepEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), sweepOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [expiryActions] = PublicKey.findProgramAddressSync(
+        [Buffer.from("actions"), sweepSwitch.toBuffer()],
+        program.programId
+      );
+
+      const mint = await createMint(
+        provider.connection,
+        sweepOwner,
+        sweepOwner.publicKey,
+        null,
+        0
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { spl: { mint } }
+        )
+        .accounts({
+          switch: sweepSwitch,
+          escrow: sweepEscrow,
+          owner: sweepOwner.publicKey,
+          payer: sweepOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([sweepOwner])
+        .rpc();
+
+      await program.methods
+        .setFallback(switchId, fallback.publicKey, new BN(1))
+        .accounts({
+          switch: sweepSwitch,
+          owner: sweepOwner.publicKey,
+        })
+        .signers([sweepOwner])
+        .rpc();
+
+      // Left unexecuted, so the sweep has to close it
+      const transferIx = anchor.web3.SystemProgram.transfer({
+        fromPubkey: sweepEscrow,
+        toPubkey: fallback.publicKey,
+        lamports: 1,
+      });
+      await program.methods
+        .storeExpiryActions(switchId, [
+          {
+            programId: transferIx.programId,
+            accounts: transferIx.keys.map(k => ({
+              pubkey: k.pubkey,
+              isSigner: k.isSigner,
+              isWritable: k.isWritable,
+            })),
+            data: transferIx.data,
+            executed: false,
+          },
+        ])
+        .accounts({
+          switch: sweepSwitch,
+          escrow: sweepEscrow,
+          expiryActions,
+          owner: sweepOwner.publicKey,
+        })
+        .signers([sweepOwner])
+        .rpc();
+
+      const escrowTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        sweepOwner,
+        mint,
+        sweepEscrow,
+        true
+      );
+      const fallbackTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        sweepOwner,
+        mint,
+        fallback.publicKey
+      );
+      await mintTo(provider.connection, sweepOwner, mint, escrowTokens.address, sweepOwner, 1000);
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: sweepSwitch, profile: null })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      const sweep = (tokenPairs: PublicKey[]) =>
+        program.methods
+          .sweepUnclaimed()
+          .accounts({
+            switch: sweepSwitch,
+            escrow: sweepEscrow,
+            fallback: fallback.publicKey,
+            expiryActions,
+            owner: sweepOwner.publicKey,
+          })
+          .remainingAccounts([
+            ...tokenPairs.map(pubkey => ({ pubkey, isSigner: false, isWritable: true })),
+            ...beneficiaryIndexes,
+          ])
+          .rpc();
+
+      // Closing the switch without the token accounts would strand the tokens
+      try {
+        await sweep([]);
+        expect.fail("Should require the escrow's token account");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SweepTokenAccountRequired");
+      }
+
+      await sweep([escrowTokens.address, fallbackTokens.address]);
+
+      expect(Number((await getAccount(provider.connection, fallbackTokens.address)).amount)).to.equal(1000);
+      expect(await provider.connection.getAccountInfo(escrowTokens.address)).to.be.null;
+      expect(await provider.connection.getAccountInfo(expiryActions)).to.be.null;
+      expect(await provider.connection.getAccountInfo(sweepSwitch)).to.be.null;
+    });
+  });
+
+  describe("revive_switch", () => {
+    it("Blocks distribution and lets the owner revive during the challenge period", async () => {
+      const reviveOwner = Keypair.generate();
+      const switchId = "revive";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        reviveOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [reviveSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), reviveOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [reviveEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), reviveOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: reviveSwitch,
+          escrow: reviveEscrow,
+          owner: reviveOwner.publicKey,
+          payer: reviveOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([reviveOwner])
+        .rpc();
+
+      // An unbounded period would hold distribution off forever
+      try {
+        await program.methods
+          .setChallengePeriod(switchId, new BN(91 * 86400))
+          .accounts({
+            switch: reviveSwitch,
+            owner: reviveOwner.publicKey,
+          })
+          .signers([reviveOwner])
+          .rpc();
+        expect.fail("Should have rejected a challenge period over 90 days");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidChallengePeriod");
+      }
+
+      await program.methods
+        .setChallengePeriod(switchId, new BN(3600))
+        .accounts({
+          switch: reviveSwitch,
+          owner: reviveOwner.publicKey,
+        })
+        .signers([reviveOwner])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: reviveSwitch, profile: null })
+        .rpc();
+
+      try {
+        await program.methods
+          .distributeSol()
+          .accounts({
+            switch: reviveSwitch,
+            escrow: reviveEscrow,
+            beneficiary: beneficiary1.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have failed - challenge period is active");
+      } catch (error: any) {
+        expect(error.message).to.include("ChallengePeriodActive");
+      }
+
+      await program.methods
+        .reviveSwitch(switchId, null)
+        .accounts({
+          switch: reviveSwitch,
+          profile: null,
+          owner: reviveOwner.publicKey,
+        })
+        .signers([reviveOwner])
+        .rpc();
+
+      const switchAccount = await program.account.switch.fetch(reviveSwitch);
+      expect(switchAccount.status).to.have.property("active");
+      expect(switchAccount.heartbeatDeadline.toNumber()).to.be.greaterThan(
+        Math.floor(Date.now() / 1000) - 5
+      );
+    });
+  });
+
+  describe("joint switches", () => {
+    it("Keeps an all-policy switch alive while any owner heartbeats", async () => {
+      const spouse1 = Keypair.generate();
+      const spouse2 = Keypair.generate();
+      const switchId = "joint";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        spouse1.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [jointSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), spouse1.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [jointEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), spouse1.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(2),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: jointSwitch,
+          escrow: jointEscrow,
+          owner: spouse1.publicKey,
+          payer: spouse1.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([spouse1])
+        .rpc();
+
+      await program.methods
+        .setJointOwners(switchId, [spouse2.publicKey], { all: {} }, 2)
+        .accounts({
+          switch: jointSwitch,
+          owner: spouse1.publicKey,
+        })
+        .signers([spouse1])
+        .rpc();
+
+      // Only the second spouse checks in; the primary owner's deadline lapses
+      await new Promise(resolve => setTimeout(resolve, 1500));
+      await program.methods
+        .sendCoOwnerHeartbeat(switchId)
+        .accounts({
+          switch: jointSwitch,
+          profile: null,
+          coOwner: spouse2.publicKey,
+        })
+        .signers([spouse2])
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 1500));
+
+      try {
+        await program.methods
+          .triggerExpiry(switchId)
+          .accounts({ switch: jointSwitch, profile: null })
+          .rpc();
+        expect.fail("Should have failed - one owner is still alive");
+      } catch (error: any) {
+        expect(error.message).to.include("DeadlineNotPassed");
+      }
+
+      // Co-owner heartbeats carry no preimage, so a chain would not bind them
+      try {
+        await program.methods
+          .setHeartbeatChain(switchId, Array.from(randomBytes(32)))
+          .accounts({
+            switch: jointSwitch,
+            owner: spouse1.publicKey,
+          })
+          .signers([spouse1])
+          .rpc();
+        expect.fail("Should have failed - joint switches cannot be chained");
+      } catch (error: any) {
+        expect(error.message).to.include("JointSwitchNotChainable");
+      }
+
+      // Cancel without the second spouse's signature misses the quorum
+      try {
+        await program.methods
+          .cancelSwitch(switchId)
+          .accounts({
+            switch: jointSwitch,
+            owner: spouse1.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([spouse1])
+          .rpc();
+        expect.fail("Should have failed - quorum of 2 not met");
+      } catch (error: any) {
+        expect(error.message).to.include("OwnerQuorumNotMet");
+      }
+
+      await program.methods
+        .cancelSwitch(switchId)
+        .accounts({
+          switch: jointSwitch,
+          owner: spouse1.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: spouse2.publicKey, isSigner: true, isWritable: false },
+          ...beneficiaryIndexes,
+        ])
+        .signers([spouse1, spouse2])
+        .rpc();
+    });
+  });
+
+  describe("get_status", () => {
+    it("Returns the effective status through simulation", async () => {
+      const statusOwner = Keypair.generate();
+      const switchId = "status";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        statusOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [statusSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), statusOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [statusEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), statusOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [
+            { address: beneficiary1.publicKey, shareBps: 7500 },
+            { address: beneficiary2.publicKey, shareBps: 2500 },
+          ],
+          { sol: {} }
+        )
+        .accounts({
+          switch: statusSwitch,
+          escrow: statusEscrow,
+          owner: statusOwner.publicKey,
+          payer: statusOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([statusOwner])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      // Deadline has passed but trigger_expiry has not been called
+      const status = await program.methods
+        .getStatus()
+        .accounts({
+          switch: statusSwitch,
+          escrow: statusEscrow,
+          escrowTokenAccount: null,
+          profile: null,
+        })
+        .view();
+
+      expect(status.status).to.have.property("expired");
+      expect(status.secondsRemaining.toNumber()).to.equal(0);
+      expect(status.entitlements).to.have.lengthOf(2);
+      expect(status.entitlements[0].shareBps).to.equal(7500);
+      expect(status.entitlements[0].amount).to.not.be.null;
+    });
+  });
+
+  describe("stake inheritance", () => {
+    it("Moves stake authorities to the escrow and back to the owner", async () => {
+      const stakeOwner = Keypair.generate();
+      const stakeAccount = Keypair.generate();
+      const switchId = "stake";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        stakeOwner.publicKey,
+        LAMPORTS_PER_SOL * 3
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [stakeSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), stakeOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [stakeEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), stakeOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: stakeSwitch,
+          escrow: stakeEscrow,
+          owner: stakeOwner.publicKey,
+          payer: stakeOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([stakeOwner])
+        .rpc();
+
+      // Native stake account with the owner as staker and withdrawer
+      const createStakeTx = anchor.web3.StakeProgram.createAccount({
+        fromPubkey: stakeOwner.publicKey,
+        stakePubkey: stakeAccount.publicKey,
+        authorized: new anchor.web3.Authorized(stakeOwner.publicKey, stakeOwner.publicKey),
+        lamports: LAMPORTS_PER_SOL,
+      });
+      await provider.sendAndConfirm(createStakeTx, [stakeOwner, stakeAccount]);
+
+      await program.methods
+        .depositStake(switchId, beneficiary1.publicKey)
+        .accounts({
+          switch: stakeSwitch,
+          escrow: stakeEscrow,
+          stakeAccount: stakeAccount.publicKey,
+          owner: stakeOwner.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          stakeProgram: anchor.web3.StakeProgram.programId,
+        })
+        .signers([stakeOwner])
+        .rpc();
+
+      let parsed: any = (await provider.connection.getParsedAccountInfo(stakeAccount.publicKey))
+        .value.data;
+      expect(parsed.parsed.info.meta.authorized.staker).to.equal(stakeEscrow.toString());
+      expect(parsed.parsed.info.meta.authorized.withdrawer).to.equal(stakeEscrow.toString());
+
+      const switchAccount = await program.account.switch.fetch(stakeSwitch);
+      expect(switchAccount.stakeAccounts).to.have.lengthOf(1);
+
+      // Cancel is blocked until the stake account is reclaimed
+      try {
+        await program.methods
+          .cancelSwitch(switchId)
+          .accounts({
+            switch: stakeSwitch,
+            owner: stakeOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([stakeOwner])
+          .rpc();
+        expect.fail("Should have failed - stake account still registered");
+      } catch (error: any) {
+        expect(error.message).to.include("StakeAccountsRegistered");
+      }
+
+      await program.methods
+        .reclaimStake(switchId)
+        .accounts({
+          switch: stakeSwitch,
+          escrow: stakeEscrow,
+          stakeAccount: stakeAccount.publicKey,
+          owner: stakeOwner.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          stakeProgram: anchor.web3.StakeProgram.programId,
+        })
+        .signers([stakeOwner])
+        .rpc();
+
+      parsed = (await provider.connection.getParsedAccountInfo(stakeAccount.publicKey)).value.data;
+      expect(parsed.parsed.info.meta.authorized.staker).to.equal(stakeOwner.publicKey.toString());
+      expect(parsed.parsed.info.meta.authorized.withdrawer).to.equal(
+        stakeOwner.publicKey.toString()
+      );
+    });
+  });
+
+  describe("authority handoff", () => {
+    it("Holds a mint authority in escrow and returns it to the owner", async () => {
+      const authorityOwner = Keypair.generate();
+      const switchId = "authority";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        authorityOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [authoritySwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), authorityOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [authorityEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), authorityOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: authoritySwitch,
+          escrow: authorityEscrow,
+          owner: authorityOwner.publicKey,
+          payer: authorityOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([authorityOwner])
+        .rpc();
+
+      const mint = await createMint(
+        provider.connection,
+        authorityOwner,
+        authorityOwner.publicKey,
+        authorityOwner.publicKey,
+        6
+      );
+
+      await program.methods
+        .depositAuthority(switchId, { mintTokens: {} }, mint, beneficiary1.publicKey)
+        .accounts({
+          switch: authoritySwitch,
+          escrow: authorityEscrow,
+          targetAccount: mint,
+          owner: authorityOwner.publicKey,
+        })
+        .signers([authorityOwner])
+        .rpc();
+
+      let mintInfo = await getMint(provider.connection, mint);
+      expect(mintInfo.mintAuthority.toString()).to.equal(authorityEscrow.toString());
+
+      // Cancel is blocked until the authority is reclaimed
+      try {
+        await program.methods
+          .cancelSwitch(switchId)
+          .accounts({
+            switch: authoritySwitch,
+            owner: authorityOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([authorityOwner])
+          .rpc();
+        expect.fail("Should have failed - authority still held");
+      } catch (error: any) {
+        expect(error.message).to.include("AuthoritiesHeld");
+      }
+
+      await program.methods
+        .reclaimAuthority(switchId, { mintTokens: {} })
+        .accounts({
+          switch: authoritySwitch,
+          escrow: authorityEscrow,
+          targetAccount: mint,
+          owner: authorityOwner.publicKey,
+        })
+        .signers([authorityOwner])
+        .rpc();
+
+      mintInfo = await getMint(provider.connection, mint);
+      expect(mintInfo.mintAuthority.toString()).to.equal(authorityOwner.publicKey.toString());
+
+      const switchAccount = await program.account.switch.fetch(authoritySwitch);
+      expect(switchAccount.heldAuthorities).to.have.lengthOf(0);
+    });
+  });
+
+  describe("expiry actions", () => {
+    it("Runs a stored instruction once after expiry", async () => {
+      const actionOwner = Keypair.generate();
+      const recipient = Keypair.generate();
+      const switchId = "actions";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        actionOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [actionSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), actionOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [actionEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), actionOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [expiryActions] = PublicKey.findProgramAddressSync(
+        [Buffer.from("actions"), actionSwitch.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: actionSwitch,
+          escrow: actionEscrow,
+          owner: actionOwner.publicKey,
+          payer: actionOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([actionOwner])
+        .rpc();
+
+      // A system transfer out of the escrow, signed by the escrow PDA
+      const transferIx = anchor.web3.SystemProgram.transfer({
+        fromPubkey: actionEscrow,
+        toPubkey: recipient.publicKey,
+        lamports: 0.1 * LAMPORTS_PER_SOL,
+      });
+
+      await program.methods
+        .storeExpiryActions(switchId, [
+          {
+            programId: transferIx.programId,
+            accounts: transferIx.keys.map(k => ({
+              pubkey: k.pubkey,
+              isSigner: k.isSigner,
+              isWritable: k.isWritable,
+            })),
+            data: transferIx.data,
+            executed: false,
+          },
+        ])
+        .accounts({
+          switch: actionSwitch,
+          escrow: actionEscrow,
+          expiryActions,
+          owner: actionOwner.publicKey,
+        })
+        .signers([actionOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: actionOwner.publicKey,
+            toPubkey: actionEscrow,
+            lamports: 0.2 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [actionOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: actionSwitch, profile: null })
+        .rpc();
+
+      const remainingAccounts = [
+        { pubkey: recipient.publicKey, isSigner: false, isWritable: true },
+        { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
+      ];
+
+      await program.methods
+        .executeExpiryAction(0)
+        .accounts({
+          switch: actionSwitch,
+          escrow: actionEscrow,
+          expiryActions,
+        })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+      expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(
+        0.1 * LAMPORTS_PER_SOL
+      );
+
+      try {
+        await program.methods
+          .executeExpiryAction(0)
+          .accounts({
+            switch: actionSwitch,
+            escrow: actionEscrow,
+            expiryActions,
+          })
+          .remainingAccounts(remainingAccounts)
+          .rpc();
+        expect.fail("Should have failed - action already executed");
+      } catch (error: any) {
+        expect(error.message).to.include("ExpiryActionAlreadyExecuted");
+      }
+    });
+  });
+
+  describe("profile", () => {
+    it("Keeps linked switches alive with a single profile heartbeat", async () => {
+      const profileOwner = Keypair.generate();
+
+      const airdrop = await provider.connection.requestAirdrop(
+        profileOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [profile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("profile"), profileOwner.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeProfile(new BN(3600))
+        .accounts({
+          profile,
+          owner: profileOwner.publicKey,
+          payer: profileOwner.publicKey,
+        })
+        .signers([profileOwner])
+        .rpc();
+
+      const switches: PublicKey[] = [];
+      for (const switchId of ["profile-a", "profile-b"]) {
+        const [linkedSwitch] = PublicKey.findProgramAddressSync(
+          [Buffer.from("switch"), profileOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        );
+        const [linkedEscrow] = PublicKey.findProgramAddressSync(
+          [Buffer.from("escrow"), profileOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        );
+
+        // Each switch's own timeout is short; the profile keeps it alive
+        await program.methods
+          .initializeSwitch(
+            switchId,
+            new BN(1),
+            [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+            { sol: {} }
+          )
+          .accounts({
+            switch: linkedSwitch,
+            escrow: linkedEscrow,
+            owner: profileOwner.publicKey,
+            payer: profileOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([profileOwner])
+          .rpc();
+
+        await program.methods
+          .linkProfile(switchId)
+          .accounts({
+            switch: linkedSwitch,
+            profile,
+            owner: profileOwner.publicKey,
+          })
+          .signers([profileOwner])
+          .rpc();
+
+        switches.push(linkedSwitch);
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      const histories = switches.map(
+        linkedSwitch =>
+          PublicKey.findProgramAddressSync(
+            [Buffer.from("history"), linkedSwitch.toBuffer()],
+            program.programId
+          )[0]
+      );
+      const sendProfileHeartbeat = (linked: PublicKey[]) =>
+        program.methods
+          .sendProfileHeartbeat()
+          .accounts({
+            profile,
+            owner: profileOwner.publicKey,
+          })
+          .remainingAccounts(
+            linked.flatMap((pubkey, i) => [
+              { pubkey, isSigner: false, isWritable: true },
+              { pubkey: histories[i], isSigner: false, isWritable: true },
+            ])
+          )
+          .signers([profileOwner])
+          .rpc();
+
+      try {
+        await sendProfileHeartbeat(switches.slice(0, 1));
+        expect.fail("Every linked switch must take the heartbeat");
+      } catch (error: any) {
+        expect(error.toString()).to.include("LinkedSwitchesRequired");
+      }
+
+      await sendProfileHeartbeat(switches);
+
+      for (const history of histories) {
+        const historyAccount = await program.account.heartbeatHistory.fetch(history);
+        expect(historyAccount.records).to.have.lengthOf(1);
+        expect(historyAccount.records[0].signer.toString()).to.equal(
+          profileOwner.publicKey.toString()
+        );
+      }
+
+      // Linked switches keep their own minimum interval
+      await program.methods
+        .setMinHeartbeatInterval("profile-a", new BN(3600))
+        .accounts({
+          switch: switches[0],
+          owner: profileOwner.publicKey,
+        })
+        .signers([profileOwner])
+        .rpc();
+
+      try {
+        await sendProfileHeartbeat(switches);
+        expect.fail("Profile heartbeat came too soon for profile-a");
+      } catch (error: any) {
+        expect(error.toString()).to.include("HeartbeatTooSoon");
+      }
+
+      for (const [i, linkedSwitch] of switches.entries()) {
+        try {
+          await program.methods
+            .triggerExpiry(["profile-a", "profile-b"][i])
+            .accounts({ switch: linkedSwitch, profile })
+            .rpc();
+          expect.fail("Should have failed - profile deadline has not passed");
+        } catch (error: any) {
+          expect(error.message).to.include("DeadlineNotPassed");
+        }
+      }
+    });
+  });
+
+  describe("send_heartbeat_batch", () => {
+    it("Refreshes several switches with one signature", async () => {
+      const batchOwner = Keypair.generate();
+      const switchIds = ["batch-a", "batch-b", "batch-c"];
+
+      const airdrop = await provider.connection.requestAirdrop(
+        batchOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const switches: PublicKey[] = [];
+      for (const switchId of switchIds) {
+        const [batchSwitch] = PublicKey.findProgramAddressSync(
+          [Buffer.from("switch"), batchOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        );
+        const [batchEscrow] = PublicKey.findProgramAddressSync(
+          [Buffer.from("escrow"), batchOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        );
+
+        await program.methods
+          .initializeSwitch(
+            switchId,
+            new BN(3600),
+            [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+            { sol: {} }
+          )
+          .accounts({
+            switch: batchSwitch,
+            escrow: batchEscrow,
+            owner: batchOwner.publicKey,
+            payer: batchOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([batchOwner])
+          .rpc();
+
+        switches.push(batchSwitch);
+      }
+
+      const before = await program.account.switch.fetchMultiple(switches);
+      await new Promise(resolve => setTimeout(resolve, 1500));
+
+      const results: any[] = [];
+      const listener = program.addEventListener("batchHeartbeatResult", event => {
+        results.push(event);
+      });
+
+      await program.methods
+        .sendHeartbeatBatch()
+        .accounts({ owner: batchOwner.publicKey })
+        .remainingAccounts(
+          switches.flatMap(pubkey => [
+            { pubkey, isSigner: false, isWritable: true },
+            {
+              pubkey: PublicKey.findProgramAddressSync(
+                [Buffer.from("history"), pubkey.toBuffer()],
+                program.programId
+              )[0],
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+        )
+        .signers([batchOwner])
+        .rpc();
+
+      const after = await program.account.switch.fetchMultiple(switches);
+      after.forEach((switchAccount: any, i) => {
+        expect(switchAccount.heartbeatDeadline.toNumber()).to.be.greaterThan(
+          (before[i] as any).heartbeatDeadline.toNumber()
+        );
+      });
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+      expect(results.filter(r => r.success)).to.have.lengthOf(switchIds.length);
+    });
+  });
+
+  describe("switch registry", () => {
+    it("Creates counter-indexed switches that clients can enumerate", async () => {
+      const registryOwner = Keypair.generate();
+
+      const airdrop = await provider.connection.requestAirdrop(
+        registryOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [registry] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registry"), registryOwner.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeRegistry()
+        .accounts({
+          registry,
+          owner: registryOwner.publicKey,
+          payer: registryOwner.publicKey,
+        })
+        .signers([registryOwner])
+        .rpc();
+
+      const indexedSwitch = (index: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("switch"), registryOwner.publicKey.toBuffer(), Buffer.from(`#${index}`)],
+          program.programId
+        )[0];
+      const indexedEscrow = (index: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("escrow"), registryOwner.publicKey.toBuffer(), Buffer.from(`#${index}`)],
+          program.programId
+        )[0];
+
+      const labels = [
+        "Family estate - checking and savings",
+        "Company treasury hand-over to the board of directors",
+      ];
+      for (const [index, label] of labels.entries()) {
+        await program.methods
+          .initializeIndexedSwitch(
+            label,
+            new BN(3600),
+            [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+            { sol: {} }
+          )
+          .accounts({
+            registry,
+            switch: indexedSwitch(index),
+            escrow: indexedEscrow(index),
+            owner: registryOwner.publicKey,
+            payer: registryOwner.publicKey,
+          })
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([registryOwner])
+          .rpc();
+      }
+
+      // Enumerate without getProgramAccounts
+      const registryAccount = await program.account.registry.fetch(registry);
+      expect(registryAccount.switchCount.toNumber()).to.equal(labels.length);
+
+      for (let index = 0; index < registryAccount.switchCount.toNumber(); index++) {
+        const switchAccount = await program.account.switch.fetch(indexedSwitch(index));
+        expect(switchAccount.switchId).to.equal(`#${index}`);
+        expect(switchAccount.label).to.equal(labels[index]);
+      }
+    });
+  });
+
+  describe("beneficiary index", () => {
+    it("Tracks every switch naming a beneficiary across init and cancel", async () => {
+      const indexOwner = Keypair.generate();
+      const heir = Keypair.generate();
+      const airdrop = await provider.connection.requestAirdrop(
+        indexOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [heirIndex] = PublicKey.findProgramAddressSync(
+        [Buffer.from("beneficiary_index"), heir.publicKey.toBuffer()],
+        program.programId
+      );
+      const heirIndexAccounts = [
+        { pubkey: heirIndex, isSigner: false, isWritable: true },
+      ];
+      const switchFor = (switchId: string) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("switch"), indexOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        )[0];
+      const escrowFor = (switchId: string) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("escrow"), indexOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+          program.programId
+        )[0];
+
+      try {
+        await program.methods
+          .initializeSwitch(
+            "no-index",
+            new BN(3600),
+            [{ address: heir.publicKey, shareBps: 10000 }],
+            { sol: {} }
+          )
+          .accounts({
+            switch: switchFor("no-index"),
+            escrow: escrowFor("no-index"),
+            owner: indexOwner.publicKey,
+            payer: indexOwner.publicKey,
+          })
+          .signers([indexOwner])
+          .rpc();
+
+        expect.fail("Should have failed without the beneficiary index");
+      } catch (error: any) {
+        expect(error.toString()).to.include("BeneficiaryIndexMissing");
+      }
+
+      for (const switchId of ["first", "second"]) {
+        await program.methods
+          .initializeSwitch(
+            switchId,
+            new BN(3600),
+            [{ address: heir.publicKey, shareBps: 10000 }],
+            { sol: {} }
+          )
+          .accounts({
+            switch: switchFor(switchId),
+            escrow: escrowFor(switchId),
+            owner: indexOwner.publicKey,
+            payer: indexOwner.publicKey,
+          })
+          .remainingAccounts(heirIndexAccounts)
+          .signers([indexOwner])
+          .rpc();
+      }
+
+      let index = await program.account.beneficiaryIndex.fetch(heirIndex);
+      expect(index.beneficiary.toString()).to.equal(heir.publicKey.toString());
+      expect(index.switches.map(k => k.toString())).to.deep.equal([
+        switchFor("first").toString(),
+        switchFor("second").toString(),
+      ]);
+
+      await program.methods
+        .cancelSwitch("first")
+        .accounts({
+          switch: switchFor("first"),
+          owner: indexOwner.publicKey,
+        })
+        .remainingAccounts(heirIndexAccounts)
+        .signers([indexOwner])
+        .rpc();
+
+      index = await program.account.beneficiaryIndex.fetch(heirIndex);
+      expect(index.switches.map(k => k.toString())).to.deep.equal([
+        switchFor("second").toString(),
+      ]);
+    });
+  });
+
+  describe("heartbeat schedule", () => {
+    it("Aligns deadlines to calendar months and fixed intervals", async () => {
+      const scheduleOwner = Keypair.generate();
+      const switchId = "schedule";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        scheduleOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [scheduleSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), scheduleOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [scheduleEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), scheduleOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: scheduleSwitch,
+          escrow: scheduleEscrow,
+          owner: scheduleOwner.publicKey,
+          payer: scheduleOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([scheduleOwner])
+        .rpc();
+
+      try {
+        await program.methods
+          .setHeartbeatSchedule(switchId, { fixedPeriod: { months: 0 } })
+          .accounts({
+            switch: scheduleSwitch,
+            owner: scheduleOwner.publicKey,
+          })
+          .signers([scheduleOwner])
+          .rpc();
+
+        expect.fail("Should have rejected a zero-month period");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidHeartbeatSchedule");
+      }
+
+      // Once per calendar month: due by the end of next month
+      await program.methods
+        .setHeartbeatSchedule(switchId, { fixedPeriod: { months: 1 } })
+        .accounts({
+          switch: scheduleSwitch,
+          owner: scheduleOwner.publicKey,
+        })
+        .signers([scheduleOwner])
+        .rpc();
+
+      const slot = await provider.connection.getSlot();
+      const now = (await provider.connection.getBlockTime(slot))!;
+      const today = new Date(now * 1000);
+      const endOfNextMonth =
+        Date.UTC(today.getUTCFullYear(), today.getUTCMonth() + 2, 1) / 1000;
+
+      let switchAccount = await program.account.switch.fetch(scheduleSwitch);
+      expect(switchAccount.heartbeatSchedule).to.deep.equal({ fixedPeriod: { months: 1 } });
+      expect(switchAccount.heartbeatDeadline.toNumber()).to.equal(endOfNextMonth);
+
+      // Another heartbeat in the same month does not push the deadline further
+      await program.methods
+        .sendHeartbeat(switchId)
+        .accounts({
+          switch: scheduleSwitch,
+          owner: scheduleOwner.publicKey,
+        })
+        .signers([scheduleOwner])
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(scheduleSwitch);
+      expect(switchAccount.heartbeatDeadline.toNumber()).to.equal(endOfNextMonth);
+
+      // Weekly windows starting Monday 00:00 UTC (1970-01-05)
+      const week = 7 * 24 * 3600;
+      await program.methods
+        .setHeartbeatSchedule(switchId, {
+          alignedInterval: { intervalSeconds: new BN(week), offsetSeconds: new BN(4 * 24 * 3600) },
+        })
+        .accounts({
+          switch: scheduleSwitch,
+          owner: scheduleOwner.publicKey,
+        })
+        .signers([scheduleOwner])
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(scheduleSwitch);
+      const deadline = switchAccount.heartbeatDeadline.toNumber();
+      expect(new Date(deadline * 1000).getUTCDay()).to.equal(1);
+      expect(deadline - now).to.be.greaterThan(week).and.at.most(2 * week);
+    });
+  });
+
+  describe("heartbeat rate limiting", () => {
+    it("Records heartbeats and rejects them inside the minimum interval", async () => {
+      const limitOwner = Keypair.generate();
+      const switchId = "rate-limit";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        limitOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [limitSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), limitOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [limitEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), limitOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: limitSwitch,
+          escrow: limitEscrow,
+          owner: limitOwner.publicKey,
+          payer: limitOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([limitOwner])
+        .rpc();
+
+      let switchAccount = await program.account.switch.fetch(limitSwitch);
+      const createdAt = switchAccount.lastHeartbeatAt.toNumber();
+      expect(createdAt).to.be.greaterThan(0);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(0);
+
+      try {
+        await program.methods
+          .setMinHeartbeatInterval(switchId, new BN(3600))
+          .accounts({
+            switch: limitSwitch,
+            owner: limitOwner.publicKey,
+          })
+          .signers([limitOwner])
+          .rpc();
+
+        expect.fail("Should have rejected an interval as long as the timeout");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidHeartbeatInterval");
+      }
+
+      await program.methods
+        .setMinHeartbeatInterval(switchId, new BN(600))
+        .accounts({
+          switch: limitSwitch,
+          owner: limitOwner.publicKey,
+        })
+        .signers([limitOwner])
+        .rpc();
+
+      // Creation counts as the first check-in, so an immediate heartbeat is spam
+      try {
+        await program.methods
+          .sendHeartbeat(switchId)
+          .accounts({
+            switch: limitSwitch,
+            owner: limitOwner.publicKey,
+          })
+          .signers([limitOwner])
+          .rpc();
+
+        expect.fail("Should have rejected a heartbeat inside the interval");
+      } catch (error: any) {
+        expect(error.toString()).to.include("HeartbeatTooSoon");
+      }
+
+      await program.methods
+        .setMinHeartbeatInterval(switchId, new BN(0))
+        .accounts({
+          switch: limitSwitch,
+          owner: limitOwner.publicKey,
+        })
+        .signers([limitOwner])
+        .rpc();
+
+      await program.methods
+        .sendHeartbeat(switchId)
+        .accounts({
+          switch: limitSwitch,
+          owner: limitOwner.publicKey,
+        })
+        .signers([limitOwner])
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(limitSwitch);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(1);
+      expect(switchAccount.lastHeartbeatAt.toNumber()).to.be.at.least(createdAt);
+      expect(switchAccount.minHeartbeatIntervalSeconds.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("heartbeat history", () => {
+    it("Records every heartbeat with its slot, signer and method", async () => {
+      const historyOwner = Keypair.generate();
+      const switchId = "history";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        historyOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [historySwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), historyOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [historyEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), historyOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [history] = PublicKey.findProgramAddressSync(
+        [Buffer.from("history"), historySwitch.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: historySwitch,
+          escrow: historyEscrow,
+          owner: historyOwner.publicKey,
+          payer: historyOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([historyOwner])
+        .rpc();
+
+      let historyAccount = await program.account.heartbeatHistory.fetch(history);
+      expect(historyAccount.switch.toString()).to.equal(historySwitch.toString());
+      expect(historyAccount.records).to.be.empty;
+
+      await program.methods
+        .sendHeartbeat(switchId)
+        .accounts({
+          switch: historySwitch,
+          owner: historyOwner.publicKey,
+        })
+        .signers([historyOwner])
+        .rpc();
+
+      // Changing the schedule counts as a heartbeat too
+      await program.methods
+        .setHeartbeatSchedule(switchId, { sliding: {} })
+        .accounts({
+          switch: historySwitch,
+          owner: historyOwner.publicKey,
+        })
+        .signers([historyOwner])
+        .rpc();
+
+      historyAccount = await program.account.heartbeatHistory.fetch(history);
+      expect(historyAccount.records).to.have.lengthOf(2);
+      expect(historyAccount.nextIndex).to.equal(2);
+      for (const record of historyAccount.records) {
+        expect(record.signer.toString()).to.equal(historyOwner.publicKey.toString());
+        expect(record.method).to.have.property("direct");
+        expect(record.slot.toNumber()).to.be.greaterThan(0);
+      }
+      expect(historyAccount.records[1].slot.toNumber()).to.be.at.least(
+        historyAccount.records[0].slot.toNumber()
+      );
+    });
+  });
+
+  describe("challenge_liveness", () => {
+    it("Lets beneficiaries challenge the owner, rate-limited per beneficiary", async () => {
+      const challengeOwner = Keypair.generate();
+      const stranger = Keypair.generate();
+      const switchId = "challenge";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        challengeOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [challengeSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), challengeOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [challengeEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), challengeOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      // A year-long timeout that a challenge can cut short
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(365 * 24 * 3600),
+          [
+            { address: beneficiary1.publicKey, shareBps: 5000 },
+            { address: beneficiary2.publicKey, shareBps: 5000 },
+          ],
+          { sol: {} }
+        )
+        .accounts({
+          switch: challengeSwitch,
+          escrow: challengeEscrow,
+          owner: challengeOwner.publicKey,
+          payer: challengeOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([challengeOwner])
+        .rpc();
+
+      const challenge = (beneficiary: Keypair) =>
+        program.methods
+          .challengeLiveness(switchId)
+          .accounts({
+            switch: challengeSwitch,
+            profile: null,
+            beneficiary: beneficiary.publicKey,
+          })
+          .signers([beneficiary])
+          .rpc();
+
+      try {
+        await challenge(stranger);
+        expect.fail("Only beneficiaries may challenge");
+      } catch (error: any) {
+        expect(error.toString()).to.include("BeneficiaryNotFound");
+      }
+
+      await challenge(beneficiary1);
+
+      let switchAccount = await program.account.switch.fetch(challengeSwitch);
+      const challengedAt = switchAccount.livenessChallengedAt.toNumber();
+      expect(challengedAt).to.be.greaterThan(0);
+
+      // The challenge deadline replaces the year-long one while unanswered
+      const status = await program.methods
+        .getStatus()
+        .accounts({
+          switch: challengeSwitch,
+          escrow: challengeEscrow,
+          escrowTokenAccount: null,
+          profile: null,
+        })
+        .view();
+      expect(status.heartbeatDeadline.toNumber()).to.equal(challengedAt + 14 * 24 * 3600);
+
+      try {
+        await challenge(beneficiary2);
+        expect.fail("Only one challenge may be pending");
+      } catch (error: any) {
+        expect(error.toString()).to.include("LivenessChallengePending");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.methods
+        .sendHeartbeat(switchId)
+        .accounts({
+          switch: challengeSwitch,
+          owner: challengeOwner.publicKey,
+        })
+        .signers([challengeOwner])
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(challengeSwitch);
+      expect(switchAccount.heartbeatDeadline.toNumber()).to.be.greaterThan(
+        challengedAt + 300 * 24 * 3600
+      );
+
+      try {
+        await challenge(beneficiary1);
+        expect.fail("Beneficiary is still cooling down");
+      } catch (error: any) {
+        expect(error.toString()).to.include("LivenessChallengeCooldown");
+      }
+
+      // Another beneficiary has their own allowance
+      await program.methods
+        .setMinHeartbeatInterval(switchId, new BN(24 * 3600))
+        .accounts({ switch: challengeSwitch, owner: challengeOwner.publicKey })
+        .signers([challengeOwner])
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await challenge(beneficiary2);
+
+      // The answer is exempt from the minimum interval, later heartbeats are not
+      const heartbeat = () =>
+        program.methods
+          .sendHeartbeat(switchId)
+          .accounts({
+            switch: challengeSwitch,
+            owner: challengeOwner.publicKey,
+          })
+          .signers([challengeOwner])
+          .rpc();
+
+      await heartbeat();
+      switchAccount = await program.account.switch.fetch(challengeSwitch);
+      expect(switchAccount.lastHeartbeatAt.toNumber()).to.be.at.least(
+        switchAccount.livenessChallengedAt.toNumber()
+      );
+
+      try {
+        await heartbeat();
+        expect.fail("Only the answer skips the minimum interval");
+      } catch (error: any) {
+        expect(error.toString()).to.include("HeartbeatTooSoon");
+      }
+    });
+  });
+
+  describe("executor", () => {
+    it("Lets the executor adjust shares, pay expenses and order distributions within bounds", async () => {
+      const estateOwner = Keypair.generate();
+      const executor = Keypair.generate();
+      const lawyer = Keypair.generate();
+      const stranger = Keypair.generate();
+      const switchId = "estate";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        estateOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [estateSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), estateOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [estateEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), estateOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [
+            { address: beneficiary1.publicKey, shareBps: 5000 },
+            { address: beneficiary2.publicKey, shareBps: 5000 },
+          ],
+          { sol: {} }
+        )
+        .accounts({
+          switch: estateSwitch,
+          escrow: estateEscrow,
+          owner: estateOwner.publicKey,
+          payer: estateOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([estateOwner])
+        .rpc();
+
+      await program.methods
+        .setExecutor(
+          switchId,
+          executor.publicKey,
+          1000,
+          new BN(3600),
+          [{ payee: lawyer.publicKey, capLamports: new BN(0.01 * LAMPORTS_PER_SOL), paidLamports: new BN(0) }]
+        )
+        .accounts({
+          switch: estateSwitch,
+          owner: estateOwner.publicKey,
+        })
+        .signers([estateOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: estateOwner.publicKey,
+            toPubkey: estateEscrow,
+            lamports: 0.2 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [estateOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: estateSwitch, profile: null })
+        .rpc();
+
+      const distribute = (beneficiary: Keypair) =>
+        program.methods
+          .distributeSol()
+          .accounts({
+            switch: estateSwitch,
+            escrow: estateEscrow,
+            beneficiary: beneficiary.publicKey,
+          })
+          .rpc();
+
+      try {
+        await distribute(beneficiary1);
+        expect.fail("Distribution must wait for the executor");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ExecutorWindowOpen");
+      }
+
+      const executorAccounts = { switch: estateSwitch, executor: executor.publicKey };
+
+      try {
+        await program.methods
+          .executorAdjustShares([1500, -1500])
+          .accounts(executorAccounts)
+          .signers([executor])
+          .rpc();
+        expect.fail("Adjustment exceeds the owner's bound");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidShareAdjustment");
+      }
+
+      await program.methods
+        .executorAdjustShares([-1000, 1000])
+        .accounts(executorAccounts)
+        .signers([executor])
+        .rpc();
+
+      const payExpense = (payee: PublicKey, lamports: number) =>
+        program.methods
+          .executorPayExpense(new BN(lamports))
+          .accounts({
+            switch: estateSwitch,
+            escrow: estateEscrow,
+            payee,
+            executor: executor.publicKey,
+          })
+          .signers([executor])
+          .rpc();
+
+      try {
+        await payExpense(stranger.publicKey, 0.005 * LAMPORTS_PER_SOL);
+        expect.fail("Executor cannot pay unapproved addresses");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ExpenseNotApproved");
+      }
+
+      try {
+        await payExpense(lawyer.publicKey, 0.02 * LAMPORTS_PER_SOL);
+        expect.fail("Expense is capped");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ExpenseCapExceeded");
+      }
+
+      await payExpense(lawyer.publicKey, 0.005 * LAMPORTS_PER_SOL);
+      expect(await provider.connection.getBalance(lawyer.publicKey)).to.equal(
+        0.005 * LAMPORTS_PER_SOL
+      );
+
+      await program.methods
+        .executorSetDistributionOrder(Buffer.from([1, 0]))
+        .accounts(executorAccounts)
+        .signers([executor])
+        .rpc();
+
+      await program.methods
+        .executorFinalize()
+        .accounts(executorAccounts)
+        .signers([executor])
+        .rpc();
+
+      const status = await program.methods
+        .getStatus()
+        .accounts({
+          switch: estateSwitch,
+          escrow: estateEscrow,
+          escrowTokenAccount: null,
+          profile: null,
+        })
+        .view();
+      expect(status.entitlements.map(e => e.shareBps)).to.deep.equal([4000, 6000]);
+
+      try {
+        await distribute(beneficiary1);
+        expect.fail("Beneficiary 2 comes first");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DistributionOutOfOrder");
+      }
+
+      const before = await provider.connection.getBalance(beneficiary2.publicKey);
+      await distribute(beneficiary2);
+      expect(await provider.connection.getBalance(beneficiary2.publicKey)).to.be.greaterThan(before);
+      await distribute(beneficiary1);
+    });
+
+    it("Holds SPL distributions to the executor's order", async () => {
+      const estateOwner = Keypair.generate();
+      const executor = Keypair.generate();
+      const switchId = "spl-estate";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        estateOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [estateSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), estateOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [estateEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), estateOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      const mint = await createMint(
+        provider.connection,
+        estateOwner,
+        estateOwner.publicKey,
+        null,
+        0
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [
+            { address: beneficiary1.publicKey, shareBps: 5000 },
+            { address: beneficiary2.publicKey, shareBps: 5000 },
+          ],
+          { spl: { mint } }
+        )
+        .accounts({
+          switch: estateSwitch,
+          escrow: estateEscrow,
+          owner: estateOwner.publicKey,
+          payer: estateOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([estateOwner])
+        .rpc();
+
+      try {
+        await program.methods
+          .setExecutor(switchId, executor.publicKey, 0, new BN(366 * 86400), [])
+          .accounts({
+            switch: estateSwitch,
+            owner: estateOwner.publicKey,
+          })
+          .signers([estateOwner])
+          .rpc();
+        expect.fail("Should have rejected an executor window over a year");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidExecutorConfig");
+      }
+
+      await program.methods
+        .setExecutor(switchId, executor.publicKey, 0, new BN(3600), [])
+        .accounts({
+          switch: estateSwitch,
+          owner: estateOwner.publicKey,
+        })
+        .signers([estateOwner])
+        .rpc();
+
+      const escrowTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        estateOwner,
+        mint,
+        estateEscrow,
+        true
+      );
+      await mintTo(provider.connection, estateOwner, mint, escrowTokens.address, estateOwner, 1000);
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: estateSwitch, profile: null })
+        .rpc();
+
+      // Without the token account the balance is unknown, not zero
+      const status = await program.methods
+        .getStatus()
+        .accounts({
+          switch: estateSwitch,
+          escrow: estateEscrow,
+          escrowTokenAccount: null,
+          profile: null,
+        })
+        .view();
+      expect(status.entitlements.map(e => e.amount)).to.deep.equal([null, null]);
+
+      const executorAccounts = { switch: estateSwitch, executor: executor.publicKey };
+      await program.methods
+        .executorSetDistributionOrder(Buffer.from([1, 0]))
+        .accounts(executorAccounts)
+        .signers([executor])
+        .rpc();
+      await program.methods
+        .executorFinalize()
+        .accounts(executorAccounts)
+        .signers([executor])
+        .rpc();
+
+      const beneficiaryTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        estateOwner,
+        mint,
+        beneficiary1.publicKey
+      );
+
+      try {
+        await program.methods
+          .distributeSpl()
+          .accounts({
+            switch: estateSwitch,
+            escrow: estateEscrow,
+            escrowTokenAccount: escrowTokens.address,
+            beneficiary: beneficiary1.publicKey,
+            beneficiaryTokenAccount: beneficiaryTokens.address,
+          })
+          .rpc();
+        expect.fail("Beneficiary 2 comes first");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DistributionOutOfOrder");
+      }
+      expect(Number((await getAccount(provider.connection, beneficiaryTokens.address)).amount)).to.equal(0);
+    });
+  });
+
+  describe("withdraw_partial", () => {
+    it("Withdraws SOL and tokens within the period limit and counts as a heartbeat", async () => {
+      const withdrawOwner = Keypair.generate();
+      const switchId = "partial";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        withdrawOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [withdrawSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), withdrawOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [withdrawEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), withdrawOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: withdrawSwitch,
+          escrow: withdrawEscrow,
+          owner: withdrawOwner.publicKey,
+          payer: withdrawOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([withdrawOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: withdrawOwner.publicKey,
+            toPubkey: withdrawEscrow,
+            lamports: 0.2 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [withdrawOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      await program.methods
+        .setWithdrawalLimit(switchId, {
+          lamportsPerPeriod: new BN(0.05 * LAMPORTS_PER_SOL),
+          tokensPerPeriod: new BN(100),
+          periodSeconds: new BN(3600),
+        })
+        .accounts({
+          switch: withdrawSwitch,
+          owner: withdrawOwner.publicKey,
+        })
+        .signers([withdrawOwner])
+        .rpc();
+
+      const withdraw = (amount: number, tokenAccounts?: { escrow: PublicKey; owner: PublicKey }) =>
+        program.methods
+          .withdrawPartial(switchId, new BN(amount))
+          .accounts({
+            switch: withdrawSwitch,
+            escrow: withdrawEscrow,
+            escrowTokenAccount: tokenAccounts?.escrow ?? null,
+            ownerTokenAccount: tokenAccounts?.owner ?? null,
+            profile: null,
+            owner: withdrawOwner.publicKey,
+          })
+          .signers([withdrawOwner])
+          .rpc();
+
+      const escrowBefore = await provider.connection.getBalance(withdrawEscrow);
+      await withdraw(0.03 * LAMPORTS_PER_SOL);
+      expect(await provider.connection.getBalance(withdrawEscrow)).to.equal(
+        escrowBefore - 0.03 * LAMPORTS_PER_SOL
+      );
+
+      let switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.status).to.have.property("active");
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(1);
+      expect(switchAccount.withdrawnLamports.toNumber()).to.equal(0.03 * LAMPORTS_PER_SOL);
+
+      try {
+        await withdraw(0.04 * LAMPORTS_PER_SOL);
+        expect.fail("Should have exceeded the period limit");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WithdrawalLimitExceeded");
+      }
+
+      await withdraw(0.02 * LAMPORTS_PER_SOL);
+
+      // Tokens have their own allowance
+      const mint = await createMint(
+        provider.connection,
+        withdrawOwner,
+        withdrawOwner.publicKey,
+        null,
+        0
+      );
+      const escrowTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        withdrawOwner,
+        mint,
+        withdrawEscrow,
+        true
+      );
+      const ownerTokens = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        withdrawOwner,
+        mint,
+        withdrawOwner.publicKey
+      );
+      await mintTo(provider.connection, withdrawOwner, mint, escrowTokens.address, withdrawOwner, 500);
+
+      const tokenAccounts = { escrow: escrowTokens.address, owner: ownerTokens.address };
+      await withdraw(60, tokenAccounts);
+      expect(Number((await getAccount(provider.connection, ownerTokens.address)).amount)).to.equal(60);
+
+      try {
+        await withdraw(50, tokenAccounts);
+        expect.fail("Should have exceeded the token limit");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WithdrawalLimitExceeded");
+      }
+
+      switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(3);
+
+      const setLimit = (lamportsPerPeriod: number, periodSeconds = 3600) =>
+        program.methods
+          .setWithdrawalLimit(switchId, {
+            lamportsPerPeriod: new BN(lamportsPerPeriod),
+            tokensPerPeriod: new BN(100),
+            periodSeconds: new BN(periodSeconds),
+          })
+          .accounts({
+            switch: withdrawSwitch,
+            owner: withdrawOwner.publicKey,
+          })
+          .signers([withdrawOwner])
+          .rpc();
+
+      // Tightening applies at once and keeps counting against the running period
+      await setLimit(0.04 * LAMPORTS_PER_SOL);
+      switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.withdrawalLimit.lamportsPerPeriod.toNumber()).to.equal(0.04 * LAMPORTS_PER_SOL);
+      expect(switchAccount.withdrawnLamports.toNumber()).to.equal(0.05 * LAMPORTS_PER_SOL);
+      expect(switchAccount.pendingConfigChange).to.be.null;
+
+      // A longer period is tightening too, and must not hand out a fresh allowance
+      await setLimit(0.04 * LAMPORTS_PER_SOL, 7200);
+      switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.withdrawalLimit.periodSeconds.toNumber()).to.equal(7200);
+      expect(switchAccount.withdrawnLamports.toNumber()).to.equal(0.05 * LAMPORTS_PER_SOL);
+
+      try {
+        await withdraw(0.01 * LAMPORTS_PER_SOL);
+        expect.fail("Should still count the withdrawals made before the period change");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WithdrawalLimitExceeded");
+      }
+
+      // Loosening waits out a full period, so a stolen key cannot lift the cap and drain at once
+      await setLimit(LAMPORTS_PER_SOL);
+      switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.withdrawalLimit.lamportsPerPeriod.toNumber()).to.equal(0.04 * LAMPORTS_PER_SOL);
+      const pending = switchAccount.pendingConfigChange;
+      expect(pending.change).to.have.property("withdrawalLimit");
+      expect(pending.activatesAt.toNumber() - pending.proposedAt.toNumber()).to.equal(7200);
+
+      try {
+        await withdraw(0.01 * LAMPORTS_PER_SOL);
+        expect.fail("Should still be capped by the old limit");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WithdrawalLimitExceeded");
+      }
+
+      try {
+        await program.methods
+          .applyPendingConfigChange(switchId)
+          .accounts({
+            switch: withdrawSwitch,
+            profile: null,
+            payer: provider.wallet.publicKey,
+          })
+          .rpc();
+        expect.fail("Should wait for the period to pass");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConfigChangeDelayActive");
+      }
+    });
+
+    it("Withdraws outside the heartbeat interval and without a preimage on chained switches", async () => {
+      const withdrawOwner = Keypair.generate();
+      const switchId = "partial-chain";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        withdrawOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [withdrawSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), withdrawOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [withdrawEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), withdrawOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: withdrawSwitch,
+          escrow: withdrawEscrow,
+          owner: withdrawOwner.publicKey,
+          payer: withdrawOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([withdrawOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: withdrawOwner.publicKey,
+            toPubkey: withdrawEscrow,
+            lamports: 0.2 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [withdrawOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      const ownerAccounts = { switch: withdrawSwitch, owner: withdrawOwner.publicKey };
+      await program.methods
+        .setMinHeartbeatInterval(switchId, new BN(600))
+        .accounts(ownerAccounts)
+        .signers([withdrawOwner])
+        .rpc();
+
+      const withdraw = () =>
+        program.methods
+          .withdrawPartial(switchId, new BN(1000))
+          .accounts({
+            ...ownerAccounts,
+            escrow: withdrawEscrow,
+            escrowTokenAccount: null,
+            ownerTokenAccount: null,
+            profile: null,
+          })
+          .signers([withdrawOwner])
+          .rpc();
+
+      // The minimum interval limits heartbeats, not withdrawals
+      await withdraw();
+      await withdraw();
+      let switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(2);
+
+      // A chained switch still lets the owner withdraw, but only a preimage keeps it alive
+      const tip = createHash("sha256").update(randomBytes(32)).digest();
+      await program.methods
+        .setHeartbeatChain(switchId, Array.from(tip))
+        .accounts(ownerAccounts)
+        .signers([withdrawOwner])
+        .rpc();
+
+      const deadline = switchAccount.heartbeatDeadline.toNumber();
+      await withdraw();
+      switchAccount = await program.account.switch.fetch(withdrawSwitch);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(2);
+      expect(switchAccount.heartbeatDeadline.toNumber()).to.equal(deadline);
+    });
+  });
+
+  describe("cancel timelock", () => {
+    it("Delays cancellation and lets guardians and beneficiaries freeze it", async () => {
+      const lockedOwner = Keypair.generate();
+      const guardian = Keypair.generate();
+      const switchId = "timelock";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        lockedOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [lockedSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), lockedOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [lockedEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), lockedOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: lockedSwitch,
+          escrow: lockedEscrow,
+          owner: lockedOwner.publicKey,
+          payer: lockedOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([lockedOwner])
+        .rpc();
+
+      const fundTx = await provider.connection.sendTransaction(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: lockedOwner.publicKey,
+            toPubkey: lockedEscrow,
+            lamports: 0.5 * LAMPORTS_PER_SOL,
+          })
+        ),
+        [lockedOwner]
+      );
+      await provider.connection.confirmTransaction(fundTx);
+
+      const ownerAccounts = { switch: lockedSwitch, owner: lockedOwner.publicKey };
+
+      await program.methods
+        .setCancelTimelock(switchId, new BN(2), [guardian.publicKey], 2)
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+
+      try {
+        await program.methods
+          .setCancelTimelock(switchId, new BN(1), [guardian.publicKey], 2)
+          .accounts(ownerAccounts)
+          .signers([lockedOwner])
+          .rpc();
+        expect.fail("The timelock must not be shortened");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelTimelockLocked");
+      }
+
+      const cancel = () =>
+        program.methods
+          .cancelSwitch(switchId)
+          .accounts(ownerAccounts)
+          .remainingAccounts(beneficiaryIndexes)
+          .signers([lockedOwner])
+          .rpc();
+
+      try {
+        await cancel();
+        expect.fail("Cancel must be requested first");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelNotRequested");
+      }
+
+      // The owner key alone cannot drain the escrow around the timelock either
+      try {
+        await program.methods
+          .withdrawPartial(switchId, new BN(1000))
+          .accounts({
+            switch: lockedSwitch,
+            escrow: lockedEscrow,
+            escrowTokenAccount: null,
+            ownerTokenAccount: null,
+            profile: null,
+            owner: lockedOwner.publicKey,
+          })
+          .signers([lockedOwner])
+          .rpc();
+        expect.fail("Withdrawals must go through the timelock");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelNotRequested");
+      }
+
+      let requested: any = null;
+      const listener = program.addEventListener("cancelRequested", event => {
+        requested = event;
+      });
+      await program.methods
+        .requestCancel(switchId)
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+      expect(requested.switch.toString()).to.equal(lockedSwitch.toString());
+
+      try {
+        await cancel();
+        expect.fail("Timelock has not elapsed");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelTimelockActive");
+      }
+
+      const freeze = (voter: Keypair) =>
+        program.methods
+          .freezeCancel(switchId)
+          .accounts({ switch: lockedSwitch, voter: voter.publicKey })
+          .signers([voter])
+          .rpc();
+
+      await freeze(guardian);
+      let switchAccount = await program.account.switch.fetch(lockedSwitch);
+      expect(switchAccount.cancelFrozen).to.equal(false);
+
+      await freeze(beneficiary1);
+      switchAccount = await program.account.switch.fetch(lockedSwitch);
+      expect(switchAccount.cancelFrozen).to.equal(true);
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      try {
+        await cancel();
+        expect.fail("Frozen cancel must not execute");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelFrozen");
+      }
+
+      // Aborting does not lift the freeze, so a stolen key cannot simply request again
+      const requestCancel = () =>
+        program.methods
+          .requestCancel(switchId)
+          .accounts(ownerAccounts)
+          .signers([lockedOwner])
+          .rpc();
+
+      await program.methods
+        .abortCancel(switchId)
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+      switchAccount = await program.account.switch.fetch(lockedSwitch);
+      expect(switchAccount.cancelFrozen).to.equal(true);
+
+      try {
+        await requestCancel();
+        expect.fail("A frozen cancel must not be requested again");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CancelFrozen");
+      }
+
+      // Once a voter thaws it, the owner can start over with a fresh request
+      await program.methods
+        .thawCancel(switchId)
+        .accounts({ switch: lockedSwitch, voter: guardian.publicKey })
+        .signers([guardian])
+        .rpc();
+      switchAccount = await program.account.switch.fetch(lockedSwitch);
+      expect(switchAccount.cancelFrozen).to.equal(false);
+
+      await requestCancel();
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await cancel();
+      switchAccount = await program.account.switch.fetch(lockedSwitch);
+      expect(switchAccount.status).to.have.property("canceled");
+
+      // The switch stays open so the escrow can still sign the withdrawal
+      const ownerBefore = await provider.connection.getBalance(lockedOwner.publicKey);
+      await program.methods
+        .withdrawSol(switchId)
+        .accounts({ switch: lockedSwitch, escrow: lockedEscrow, owner: lockedOwner.publicKey })
+        .signers([lockedOwner])
+        .rpc();
+      expect(await provider.connection.getBalance(lockedOwner.publicKey)).to.be.greaterThan(
+        ownerBefore + 0.4 * LAMPORTS_PER_SOL
+      );
+    });
+
+    it("Holds config changes that could move the escrow behind the timelock", async () => {
+      const lockedOwner = Keypair.generate();
+      const thief = Keypair.generate();
+      const switchId = "timelock-config";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        lockedOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [lockedSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), lockedOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [lockedEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), lockedOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: lockedSwitch,
+          escrow: lockedEscrow,
+          owner: lockedOwner.publicKey,
+          payer: lockedOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([lockedOwner])
+        .rpc();
+
+      // No config delay, only the cancel timelock
+      const ownerAccounts = { switch: lockedSwitch, owner: lockedOwner.publicKey };
+      await program.methods
+        .setCancelTimelock(switchId, new BN(3600), [], 1)
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+
+      const expectStaged = async () => {
+        const switchAccount = await program.account.switch.fetch(lockedSwitch);
+        expect(switchAccount.pendingConfigChange).to.not.be.null;
+        expect(switchAccount.beneficiaries[0].address.toString()).to.equal(
+          beneficiary1.publicKey.toString()
+        );
+        expect(switchAccount.challengePeriodSeconds.toNumber()).to.equal(0);
+        expect(switchAccount.executor).to.be.null;
+
+        await program.methods
+          .cancelConfigChange(switchId)
+          .accounts(ownerAccounts)
+          .signers([lockedOwner])
+          .rpc();
+      };
+
+      // Redirecting the estate to a new heir
+      await program.methods
+        .proposeConfigChange(switchId, {
+          beneficiaries: { beneficiaries: [{ address: thief.publicKey, shareBps: 10000 }] },
+        })
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+      await expectStaged();
+
+      // Dropping the challenge period
+      await program.methods
+        .setChallengePeriod(switchId, new BN(7200))
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+      await expectStaged();
+
+      // Appointing an executor who pays the thief as an expense
+      await program.methods
+        .setExecutor(
+          switchId,
+          thief.publicKey,
+          0,
+          new BN(3600),
+          [{ payee: thief.publicKey, capLamports: new BN(LAMPORTS_PER_SOL), paidLamports: new BN(0) }]
+        )
+        .accounts(ownerAccounts)
+        .signers([lockedOwner])
+        .rpc();
+      await expectStaged();
+
+      // Storing an expiry action that pays the thief
+      const [expiryActions] = PublicKey.findProgramAddressSync(
+        [Buffer.from("actions"), lockedSwitch.toBuffer()],
+        program.programId
+      );
+      const transferIx = anchor.web3.SystemProgram.transfer({
+        fromPubkey: lockedEscrow,
+        toPubkey: thief.publicKey,
+        lamports: LAMPORTS_PER_SOL,
+      });
+      try {
+        await program.methods
+          .storeExpiryActions(switchId, [
+            {
+              programId: transferIx.programId,
+              accounts: transferIx.keys.map(k => ({
+                pubkey: k.pubkey,
+                isSigner: k.isSigner,
+                isWritable: k.isWritable,
+              })),
+              data: transferIx.data,
+              executed: false,
+            },
+          ])
+          .accounts({ ...ownerAccounts, escrow: lockedEscrow, expiryActions })
+          .signers([lockedOwner])
+          .rpc();
+        expect.fail("Expiry actions must be approved through the timelock");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ExpiryActionsNotApproved");
+      }
+    });
+  });
+
+  describe("staged config changes", () => {
+    const configOwner = Keypair.generate();
+
+    before(async () => {
+      const airdrop = await provider.connection.requestAirdrop(
+        configOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+    });
+
+    const createSwitch = async (switchId: string, timeoutSeconds: number) => {
+      const [configSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), configOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [configEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), configOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(timeoutSeconds),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: configSwitch,
+          escrow: configEscrow,
+          owner: configOwner.publicKey,
+          payer: configOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([configOwner])
+        .rpc();
+
+      await program.methods
+        .setConfigDelay(switchId, new BN(2))
+        .accounts({ switch: configSwitch, owner: configOwner.publicKey })
+        .signers([configOwner])
+        .rpc();
+
+      return configSwitch;
+    };
+
+    const propose = (switchId: string, configSwitch: PublicKey, change: any) =>
+      program.methods
+        .proposeConfigChange(switchId, change)
+        .accounts({ switch: configSwitch, owner: configOwner.publicKey })
+        .signers([configOwner])
+        .rpc();
+
+    const apply = (switchId: string, configSwitch: PublicKey) =>
+      program.methods
+        .applyPendingConfigChange(switchId)
+        .accounts({ switch: configSwitch, profile: null, payer: owner.publicKey })
+        .remainingAccounts(beneficiaryIndexes)
+        .rpc();
+
+    it("Applies beneficiary changes only after the delay", async () => {
+      const switchId = "config";
+      const configSwitch = await createSwitch(switchId, 3600);
+
+      try {
+        await program.methods
+          .setConfigDelay(switchId, new BN(1))
+          .accounts({ switch: configSwitch, owner: configOwner.publicKey })
+          .signers([configOwner])
+          .rpc();
+        expect.fail("The delay must not be shortened");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConfigDelayLocked");
+      }
+
+      let proposed: any = null;
+      const listener = program.addEventListener("configChangeProposed", event => {
+        proposed = event;
+      });
+      await propose(switchId, configSwitch, {
+        beneficiaries: {
+          beneficiaries: [{ address: beneficiary2.publicKey, shareBps: 10000 }],
+        },
+      });
+
+      try {
+        await apply(switchId, configSwitch);
+        expect.fail("Delay has not elapsed");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConfigChangeDelayActive");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+      expect(proposed.switch.toString()).to.equal(configSwitch.toString());
+
+      try {
+        await propose(switchId, configSwitch, { timeout: { timeoutSeconds: new BN(60) } });
+        expect.fail("Only one change can be pending");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConfigChangePending");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await apply(switchId, configSwitch);
+
+      const switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.beneficiaries.length).to.equal(1);
+      expect(switchAccount.beneficiaries[0].address.toString()).to.equal(
+        beneficiary2.publicKey.toString()
+      );
+      expect(switchAccount.pendingConfigChange).to.be.null;
+
+      const [oldIndex, newIndex] = beneficiaryIndexes.map(index => index.pubkey);
+      const oldSwitches = (await program.account.beneficiaryIndex.fetch(oldIndex)).switches;
+      const newSwitches = (await program.account.beneficiaryIndex.fetch(newIndex)).switches;
+      expect(oldSwitches.map(key => key.toString())).to.not.include(configSwitch.toString());
+      expect(newSwitches.map(key => key.toString())).to.include(configSwitch.toString());
+    });
+
+    it("Lets the owner cancel a pending change", async () => {
+      const switchId = "config";
+      const [configSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), configOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await propose(switchId, configSwitch, { timeout: { timeoutSeconds: new BN(60) } });
+      await program.methods
+        .cancelConfigChange(switchId)
+        .accounts({ switch: configSwitch, owner: configOwner.publicKey })
+        .signers([configOwner])
+        .rpc();
+
+      const switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.pendingConfigChange).to.be.null;
+      expect(switchAccount.timeoutSeconds.toNumber()).to.equal(3600);
+    });
+
+    it("Keeps the old configuration when the switch expires during the delay", async () => {
+      const switchId = "config-expiry";
+      const configSwitch = await createSwitch(switchId, 3);
+
+      await propose(switchId, configSwitch, { timeout: { timeoutSeconds: new BN(3600) } });
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      try {
+        await apply(switchId, configSwitch);
+        expect.fail("Expired switch must keep its configuration");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SwitchAlreadyExpired");
+      }
+
+      const switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.timeoutSeconds.toNumber()).to.equal(3);
+    });
+
+    it("Stages executor, challenge period and expiry action changes too", async () => {
+      const switchId = "config-estate";
+      const configSwitch = await createSwitch(switchId, 3600);
+      const ownerAccounts = { switch: configSwitch, owner: configOwner.publicKey };
+
+      await program.methods
+        .setExecutor(switchId, beneficiary2.publicKey, 500, new BN(3600), [])
+        .accounts(ownerAccounts)
+        .signers([configOwner])
+        .rpc();
+      let switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.executor).to.be.null;
+      expect(switchAccount.pendingConfigChange.change).to.have.property("executor");
+
+      await program.methods
+        .cancelConfigChange(switchId)
+        .accounts(ownerAccounts)
+        .signers([configOwner])
+        .rpc();
+
+      await program.methods
+        .setChallengePeriod(switchId, new BN(600))
+        .accounts(ownerAccounts)
+        .signers([configOwner])
+        .rpc();
+      switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.challengePeriodSeconds.toNumber()).to.equal(0);
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await apply(switchId, configSwitch);
+      switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.challengePeriodSeconds.toNumber()).to.equal(600);
+
+      // Expiry actions are too large to stage, so their hash is approved instead
+      const [configEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), configOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [expiryActions] = PublicKey.findProgramAddressSync(
+        [Buffer.from("actions"), configSwitch.toBuffer()],
+        program.programId
+      );
+      const transferIx = anchor.web3.SystemProgram.transfer({
+        fromPubkey: configEscrow,
+        toPubkey: beneficiary2.publicKey,
+        lamports: 1000,
+      });
+      const actions = [
+        {
+          programId: transferIx.programId,
+          accounts: transferIx.keys.map(k => ({
+            pubkey: k.pubkey,
+            isSigner: k.isSigner,
+            isWritable: k.isWritable,
+          })),
+          data: transferIx.data,
+          executed: false,
+        },
+      ];
+
+      // Borsh encoding of Vec<ExpiryAction>
+      const u32 = (value: number) => {
+        const buf = Buffer.alloc(4);
+        buf.writeUInt32LE(value);
+        return buf;
+      };
+      const encoded = Buffer.concat([
+        u32(actions.length),
+        ...actions.map(action =>
+          Buffer.concat([
+            action.programId.toBuffer(),
+            u32(action.accounts.length),
+            ...action.accounts.map(meta =>
+              Buffer.concat([
+                meta.pubkey.toBuffer(),
+                Buffer.from([meta.isSigner ? 1 : 0, meta.isWritable ? 1 : 0]),
+              ])
+            ),
+            u32(action.data.length),
+            action.data,
+            Buffer.from([action.executed ? 1 : 0]),
+          ])
+        ),
+      ]);
+      const actionsHash = Array.from(createHash("sha256").update(encoded).digest());
+
+      const store = () =>
+        program.methods
+          .storeExpiryActions(switchId, actions)
+          .accounts({ ...ownerAccounts, escrow: configEscrow, expiryActions })
+          .signers([configOwner])
+          .rpc();
+
+      try {
+        await store();
+        expect.fail("Actions must be approved through the delay");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ExpiryActionsNotApproved");
+      }
+
+      await propose(switchId, configSwitch, { expiryActions: { actionsHash } });
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await apply(switchId, configSwitch);
+
+      await store();
+      switchAccount = await program.account.switch.fetch(configSwitch);
+      expect(switchAccount.expiryActionsHash).to.be.null;
+    });
+  });
+
+  describe("hash-chain heartbeats", () => {
+    it("Requires each heartbeat to reveal the next preimage", async () => {
+      const chainOwner = Keypair.generate();
+      const switchId = "chained";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        chainOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      // chain[0] is the secret seed, chain[i + 1] = sha256(chain[i]), the last entry is the tip
+      const buildChain = (length: number) => {
+        const chain = [randomBytes(32)];
+        for (let i = 0; i < length; i++) {
+          chain.push(createHash("sha256").update(chain[i]).digest());
+        }
+        return chain.map(link => Array.from(link));
+      };
+      const chain = buildChain(3);
+      const reseeded = buildChain(1);
+
+      const [chainSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [chainEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: chainSwitch,
+          escrow: chainEscrow,
+          owner: chainOwner.publicKey,
+          payer: chainOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .postInstructions([
+          await program.methods
+            .setHeartbeatChain(switchId, chain[3])
+            .accounts({ switch: chainSwitch, owner: chainOwner.publicKey })
+            .instruction(),
+        ])
+        .signers([chainOwner])
+        .rpc();
+
+      const ownerAccounts = { switch: chainSwitch, owner: chainOwner.publicKey };
+      const expectError = async (promise: Promise<string>, code: string) => {
+        try {
+          await promise;
+          expect.fail(`Expected ${code}`);
+        } catch (error: any) {
+          expect(error.toString()).to.include(code);
+        }
+      };
+
+      await expectError(
+        program.methods
+          .sendHeartbeat(switchId)
+          .accounts(ownerAccounts)
+          .signers([chainOwner])
+          .rpc(),
+        "HeartbeatPreimageRequired"
+      );
+
+      const sendChained = (preimage: number[]) =>
+        program.methods
+          .sendChainedHeartbeat(switchId, preimage)
+          .accounts(ownerAccounts)
+          .signers([chainOwner])
+          .rpc();
+
+      await expectError(sendChained(chain[1]), "InvalidHeartbeatPreimage");
+
+      await sendChained(chain[2]);
+      let switchAccount = await program.account.switch.fetch(chainSwitch);
+      expect(switchAccount.heartbeatChain).to.deep.equal(chain[2]);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(1);
+
+      await expectError(
+        program.methods
+          .setHeartbeatChain(switchId, reseeded[1])
+          .accounts(ownerAccounts)
+          .signers([chainOwner])
+          .rpc(),
+        "HeartbeatChainAlreadySet"
+      );
+
+      await program.methods
+        .reseedHeartbeatChain(switchId, chain[1], reseeded[1])
+        .accounts(ownerAccounts)
+        .signers([chainOwner])
+        .rpc();
+
+      // The old chain is retired once re-seeded
+      await expectError(sendChained(chain[0]), "InvalidHeartbeatPreimage");
+
+      await sendChained(reseeded[0]);
+      switchAccount = await program.account.switch.fetch(chainSwitch);
+      expect(switchAccount.heartbeatChain).to.deep.equal(reseeded[0]);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(3);
+    });
+
+    it("Revives an expired chained switch only with the next preimage", async () => {
+      const chainOwner = Keypair.generate();
+      const switchId = "chained-revive";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        chainOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const seed = randomBytes(32);
+      const tip = createHash("sha256").update(seed).digest();
+
+      const [chainSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [chainEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const ownerAccounts = { switch: chainSwitch, owner: chainOwner.publicKey };
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(1),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: chainSwitch,
+          escrow: chainEscrow,
+          owner: chainOwner.publicKey,
+          payer: chainOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .postInstructions([
+          await program.methods
+            .setChallengePeriod(switchId, new BN(3600))
+            .accounts(ownerAccounts)
+            .instruction(),
+          await program.methods
+            .setHeartbeatChain(switchId, Array.from(tip))
+            .accounts(ownerAccounts)
+            .instruction(),
+        ])
+        .signers([chainOwner])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: chainSwitch, profile: null })
+        .rpc();
+
+      const revive = (preimage: number[] | null) =>
+        program.methods
+          .reviveSwitch(switchId, preimage)
+          .accounts({ ...ownerAccounts, profile: null })
+          .signers([chainOwner])
+          .rpc();
+
+      try {
+        await revive(null);
+        expect.fail("The key alone must not undo the expiry");
+      } catch (error: any) {
+        expect(error.toString()).to.include("HeartbeatPreimageRequired");
+      }
+
+      try {
+        await revive(Array.from(tip));
+        expect.fail("Only the next preimage revives");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidHeartbeatPreimage");
+      }
+
+      await revive(Array.from(seed));
+      const switchAccount = await program.account.switch.fetch(chainSwitch);
+      expect(switchAccount.status).to.have.property("active");
+      expect(switchAccount.heartbeatChain).to.deep.equal(Array.from(seed));
+    });
+  });
+
+  describe("duress code", () => {
+    it("Freezes owner actions on a duress heartbeat until guardians clear it", async () => {
+      const duressOwner = Keypair.generate();
+      const guardians = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const switchId = "duress";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        duressOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [duressSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), duressOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [duressEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), duressOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: duressSwitch,
+          escrow: duressEscrow,
+          owner: duressOwner.publicKey,
+          payer: duressOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([duressOwner])
+        .rpc();
+
+      const ownerAccounts = { switch: duressSwitch, owner: duressOwner.publicKey };
+      const expectError = async (promise: Promise<string>, code: string) => {
+        try {
+          await promise;
+          expect.fail(`Expected ${code}`);
+        } catch (error: any) {
+          expect(error.toString()).to.include(code);
+        }
+      };
+
+      const codeHash = Array.from(
+        createHash("sha256")
+          .update(Buffer.concat([duressSwitch.toBuffer(), Buffer.from("blue heron")]))
+          .digest()
+      );
+
+      // Only guardians can lift duress, so they have to exist first
+      await expectError(
+        program.methods
+          .setDuressCode(switchId, codeHash)
+          .accounts(ownerAccounts)
+          .signers([duressOwner])
+          .rpc(),
+        "DuressRequiresGuardians"
+      );
+
+      await program.methods
+        .setCancelTimelock(
+          switchId,
+          new BN(60),
+          guardians.map(guardian => guardian.publicKey),
+          1
+        )
+        .accounts(ownerAccounts)
+        .signers([duressOwner])
+        .rpc();
+      await program.methods
+        .setDuressCode(switchId, codeHash)
+        .accounts(ownerAccounts)
+        .signers([duressOwner])
+        .rpc();
+
+      const sendCoded = (code: string) =>
+        program.methods
+          .sendCodedHeartbeat(switchId, code, null)
+          .accounts(ownerAccounts)
+          .signers([duressOwner])
+          .rpc();
+
+      await sendCoded("any other code");
+      let switchAccount = await program.account.switch.fetch(duressSwitch);
+      expect(switchAccount.status).to.deep.equal({ active: {} });
+
+      let signaled: any = null;
+      const listener = program.addEventListener("duressSignaled", event => {
+        signaled = event;
+      });
+      await sendCoded("blue heron");
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+      expect(signaled.switch.toString()).to.equal(duressSwitch.toString());
+
+      switchAccount = await program.account.switch.fetch(duressSwitch);
+      expect(switchAccount.status).to.deep.equal({ duress: {} });
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(2);
+
+      // Heartbeats still work, cancel, withdrawals and schedule changes do not
+      await program.methods
+        .sendHeartbeat(switchId)
+        .accounts(ownerAccounts)
+        .signers([duressOwner])
+        .rpc();
+      await expectError(
+        program.methods
+          .requestCancel(switchId)
+          .accounts(ownerAccounts)
+          .signers([duressOwner])
+          .rpc(),
+        "SwitchNotActive"
+      );
+      await expectError(
+        program.methods
+          .withdrawPartial(switchId, new BN(1000))
+          .accounts({
+            ...ownerAccounts,
+            escrow: duressEscrow,
+            escrowTokenAccount: null,
+            ownerTokenAccount: null,
+            profile: null,
+          })
+          .signers([duressOwner])
+          .rpc(),
+        "SwitchNotActive"
+      );
+      await expectError(
+        program.methods
+          .setHeartbeatSchedule(switchId, { sliding: {} })
+          .accounts(ownerAccounts)
+          .signers([duressOwner])
+          .rpc(),
+        "SwitchNotActive"
+      );
+
+      const clear = (voter: Keypair) =>
+        program.methods
+          .clearDuress(switchId)
+          .accounts({ switch: duressSwitch, guardian: voter.publicKey })
+          .signers([voter])
+          .rpc();
+
+      await expectError(clear(beneficiary1), "NotGuardian");
+
+      await clear(guardians[0]);
+      switchAccount = await program.account.switch.fetch(duressSwitch);
+      expect(switchAccount.status).to.deep.equal({ duress: {} });
+
+      await clear(guardians[1]);
+      switchAccount = await program.account.switch.fetch(duressSwitch);
+      expect(switchAccount.status).to.deep.equal({ active: {} });
+      expect(switchAccount.duressAt.toNumber()).to.equal(0);
+    });
+
+    it("Signals duress on a hash-chained switch with the next preimage", async () => {
+      const chainOwner = Keypair.generate();
+      const guardian = Keypair.generate();
+      const switchId = "duress-chain";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        chainOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [chainSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [chainEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), chainOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      const seed = randomBytes(32);
+      const tip = createHash("sha256").update(seed).digest();
+      const ownerAccounts = { switch: chainSwitch, owner: chainOwner.publicKey };
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: chainSwitch,
+          escrow: chainEscrow,
+          owner: chainOwner.publicKey,
+          payer: chainOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([chainOwner])
+        .rpc();
+
+      await program.methods
+        .setCancelTimelock(switchId, new BN(60), [guardian.publicKey], 1)
+        .accounts(ownerAccounts)
+        .signers([chainOwner])
+        .rpc();
+      await program.methods
+        .setDuressCode(
+          switchId,
+          Array.from(
+            createHash("sha256")
+              .update(Buffer.concat([chainSwitch.toBuffer(), Buffer.from("blue heron")]))
+              .digest()
+          )
+        )
+        .accounts(ownerAccounts)
+        .signers([chainOwner])
+        .rpc();
+      await program.methods
+        .setHeartbeatChain(switchId, Array.from(tip))
+        .accounts(ownerAccounts)
+        .signers([chainOwner])
+        .rpc();
+
+      const sendCoded = (preimage: number[] | null) =>
+        program.methods
+          .sendCodedHeartbeat(switchId, "blue heron", preimage)
+          .accounts(ownerAccounts)
+          .signers([chainOwner])
+          .rpc();
+
+      // The key alone cannot signal duress any more than it can send a heartbeat
+      try {
+        await sendCoded(null);
+        expect.fail("Chained switches need the preimage");
+      } catch (error: any) {
+        expect(error.toString()).to.include("HeartbeatPreimageRequired");
+      }
+
+      await sendCoded(Array.from(seed));
+      const switchAccount = await program.account.switch.fetch(chainSwitch);
+      expect(switchAccount.status).to.deep.equal({ duress: {} });
+      expect(switchAccount.heartbeatChain).to.deep.equal(Array.from(seed));
+    });
+  });
+
+  describe("warrant canary", () => {
+    it("Publishes statements and flags the canary once it lapses", async () => {
+      const organization = Keypair.generate();
+      const switchId = "canary";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        organization.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [canarySwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), organization.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [canaryEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), organization.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const statementHash = (statement: string) =>
+        Array.from(createHash("sha256").update(statement).digest());
+
+      await program.methods
+        .initializeCanary(
+          switchId,
+          new BN(3),
+          statementHash("No warrants as of January"),
+          "https://example.org/canary/january.txt"
+        )
+        .accounts({
+          switch: canarySwitch,
+          escrow: canaryEscrow,
+          owner: organization.publicKey,
+          payer: organization.publicKey,
+        })
+        .signers([organization])
+        .rpc();
+
+      let switchAccount = await program.account.switch.fetch(canarySwitch);
+      expect(switchAccount.beneficiaries).to.be.empty;
+      expect(switchAccount.canary.lapsedAt.toNumber()).to.equal(0);
+
+      await program.methods
+        .publishCanary(
+          switchId,
+          statementHash("No warrants as of February"),
+          "https://example.org/canary/february.txt"
+        )
+        .accounts({ switch: canarySwitch, owner: organization.publicKey })
+        .signers([organization])
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(canarySwitch);
+      expect(switchAccount.canary.statementHash).to.deep.equal(
+        statementHash("No warrants as of February")
+      );
+      expect(switchAccount.canary.uri).to.equal("https://example.org/canary/february.txt");
+      expect(switchAccount.canary.publishedAt.toNumber()).to.equal(
+        switchAccount.lastHeartbeatAt.toNumber()
+      );
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+      await program.methods
+        .triggerExpiry(switchId)
+        .accounts({ switch: canarySwitch, profile: null })
+        .rpc();
+
+      switchAccount = await program.account.switch.fetch(canarySwitch);
+      expect(switchAccount.status).to.deep.equal({ expired: {} });
+      expect(switchAccount.canary.lapsedAt.toNumber()).to.equal(
+        switchAccount.expiredAt.toNumber()
+      );
+    });
+  });
+
+  describe("passkey heartbeats", () => {
+    const SECP256R1_PROGRAM_ID = new PublicKey("Secp256r1SigVerify1111111111111111111111111");
+    const P256_ORDER = BigInt(
+      "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+
+    // Passkey stand-in: a P-256 key signing like a WebAuthn authenticator
+    const { privateKey, publicKey } = generateKeyPairSync("ec", { namedCurve: "prime256v1" });
+    const jwk = publicKey.export({ format: "jwk" });
+    const y = Buffer.from(jwk.y!, "base64url");
+    const compressedKey = Buffer.concat([
+      Buffer.from([y[y.length - 1] % 2 === 0 ? 2 : 3]),
+      Buffer.from(jwk.x!, "base64url"),
+    ]);
+
+    const assertion = (challenge: Buffer) => {
+      const authenticatorData = Buffer.concat([
+        createHash("sha256").update("example.org").digest(), // rpIdHash
+        Buffer.from([0x05]), // user present and verified
+        Buffer.alloc(4), // sign count
+      ]);
+      const clientDataJson = Buffer.from(
+        JSON.stringify({
+          type: "webauthn.get",
+          challenge: challenge.toString("base64url"),
+          origin: "https://example.org",
+          crossOrigin: false,
+        })
+      );
+      const message = Buffer.concat([
+        authenticatorData,
+        createHash("sha256").update(clientDataJson).digest(),
+      ]);
+
+      // The precompile only accepts low-S signatures
+      const signature = sign("sha256", message, { key: privateKey, dsaEncoding: "ieee-p1363" });
+      let s = BigInt("0x" + signature.subarray(32).toString("hex"));
+      if (s > P256_ORDER / BigInt(2)) {
+        s = P256_ORDER - s;
+      }
+      const lowS = Buffer.concat([
+        signature.subarray(0, 32),
+        Buffer.from(s.toString(16).padStart(64, "0"), "hex"),
+      ]);
+
+      // One signature, offsets point into this instruction (u16::MAX)
+      const publicKeyOffset = 16;
+      const signatureOffset = publicKeyOffset + 33;
+      const messageOffset = signatureOffset + 64;
+      const header = Buffer.alloc(16);
+      header.writeUInt8(1, 0);
+      header.writeUInt16LE(signatureOffset, 2);
+      header.writeUInt16LE(0xffff, 4);
+      header.writeUInt16LE(publicKeyOffset, 6);
+      header.writeUInt16LE(0xffff, 8);
+      header.writeUInt16LE(messageOffset, 10);
+      header.writeUInt16LE(message.length, 12);
+      header.writeUInt16LE(0xffff, 14);
+
+      return {
+        authenticatorData,
+        clientDataJson,
+        verifyInstruction: new TransactionInstruction({
+          programId: SECP256R1_PROGRAM_ID,
+          keys: [],
+          data: Buffer.concat([header, compressedKey, lowS, message]),
+        }),
+      };
+    };
+
+    it("Accepts a relayed passkey assertion once", async () => {
+      const passkeyOwner = Keypair.generate();
+      const switchId = "passkey";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        passkeyOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [passkeySwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), passkeyOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [passkeyEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), passkeyOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: passkeySwitch,
+          escrow: passkeyEscrow,
+          owner: passkeyOwner.publicKey,
+          payer: passkeyOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([passkeyOwner])
+        .rpc();
+
+      await program.methods
+        .setPasskey(switchId, Array.from(compressedKey))
+        .accounts({ switch: passkeySwitch, owner: passkeyOwner.publicKey })
+        .signers([passkeyOwner])
+        .rpc();
+
+      const challenge = (nonce: number) => {
+        const nonceBytes = Buffer.alloc(8);
+        nonceBytes.writeBigUInt64LE(BigInt(nonce));
+        return createHash("sha256")
+          .update(Buffer.concat([passkeySwitch.toBuffer(), nonceBytes]))
+          .digest();
+      };
+
+      // Submitted by the provider wallet acting as relayer, not the owner
+      const relay = (signed: ReturnType<typeof assertion>) =>
+        program.methods
+          .sendPasskeyHeartbeat(switchId, signed.authenticatorData, signed.clientDataJson)
+          .accounts({ switch: passkeySwitch, instructions: SYSVAR_INSTRUCTIONS_PUBKEY })
+          .preInstructions([signed.verifyInstruction])
+          .rpc();
+
+      const first = assertion(challenge(0));
+      await relay(first);
+
+      let switchAccount = await program.account.switch.fetch(passkeySwitch);
+      expect(switchAccount.passkeyNonce.toNumber()).to.equal(1);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(1);
+
+      // The history names the passkey, not the owner
+      const [passkeyHistory] = PublicKey.findProgramAddressSync(
+        [Buffer.from("history"), passkeySwitch.toBuffer()],
+        program.programId
+      );
+      const historyAccount = await program.account.heartbeatHistory.fetch(passkeyHistory);
+      expect(historyAccount.records[0].signer.toBuffer()).to.deep.equal(
+        createHash("sha256").update(compressedKey).digest()
+      );
+
+      // A replayed assertion signs the spent nonce
+      try {
+        await relay(assertion(challenge(0)));
+        expect.fail("Replayed assertion must be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidPasskeyAssertion");
+      }
+
+      // Without the precompile the program has nothing to trust
+      try {
+        await program.methods
+          .sendPasskeyHeartbeat(switchId, first.authenticatorData, first.clientDataJson)
+          .accounts({ switch: passkeySwitch, instructions: SYSVAR_INSTRUCTIONS_PUBKEY })
+          .rpc();
+        expect.fail("Assertion must be verified by the precompile");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingSignatureVerification");
+      }
+
+      await relay(assertion(challenge(1)));
+      switchAccount = await program.account.switch.fetch(passkeySwitch);
+      expect(switchAccount.passkeyNonce.toNumber()).to.equal(2);
+    });
+  });
+
+  describe("Ethereum heartbeats", () => {
+    it("Accepts an EIP-191 signature relayed by any fee payer", async () => {
+      const ethOwner = Keypair.generate();
+      const switchId = "eth";
+
+      const airdrop = await provider.connection.requestAirdrop(
+        ethOwner.publicKey,
+        LAMPORTS_PER_SOL * 2
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const [ethSwitch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("switch"), ethOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+      const [ethEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), ethOwner.publicKey.toBuffer(), Buffer.from(switchId)],
+        program.programId
+      );
+
+      await program.methods
+        .initializeSwitch(
+          switchId,
+          new BN(3600),
+          [{ address: beneficiary1.publicKey, shareBps: 10000 }],
+          { sol: {} }
+        )
+        .accounts({
+          switch: ethSwitch,
+          escrow: ethEscrow,
+          owner: ethOwner.publicKey,
+          payer: ethOwner.publicKey,
+        })
+        .remainingAccounts(beneficiaryIndexes)
+        .signers([ethOwner])
+        .rpc();
+
+      const ethKey = createECDH("secp256k1");
+      ethKey.generateKeys();
+      const ethAddress = Secp256k1Program.publicKeyToEthAddress(
+        ethKey.getPublicKey().subarray(1)
+      );
+
+      await program.methods
+        .setEthAddress(switchId, Array.from(ethAddress))
+        .accounts({ switch: ethSwitch, owner: ethOwner.publicKey })
+        .signers([ethOwner])
+        .rpc();
+
+      // EIP-191 personal_sign payload, as a wallet would produce it
+      const signedMessage = (nonce: number) => {
+        const text = `Dead man's switch heartbeat\nSwitch: ${ethSwitch.toBase58()}\nNonce: ${nonce}`;
+        return Buffer.from(`\x19Ethereum Signed Message:\n${text.length}${text}`);
+      };
+
+      const relay = (nonce: number) =>
+        program.methods
+          .sendEthHeartbeat(switchId)
+          .accounts({ switch: ethSwitch, instructions: SYSVAR_INSTRUCTIONS_PUBKEY })
+          .preInstructions([
+            Secp256k1Program.createInstructionWithPrivateKey({
+              privateKey: ethKey.getPrivateKey(),
+              message: signedMessage(nonce),
+              instructionIndex: 0,
+            }),
+          ])
+          .rpc();
+
+      await relay(0);
+
+      let switchAccount = await program.account.switch.fetch(ethSwitch);
+      expect(switchAccount.ethNonce.toNumber()).to.equal(1);
+      expect(switchAccount.heartbeatCount.toNumber()).to.equal(1);
+
+      // The history names the Ethereum address, not the owner
+      const [ethHistory] = PublicKey.findProgramAddressSync(
+        [Buffer.from("history"), ethSwitch.toBuffer()],
+        program.programId
+      );
+      const historyAccount = await program.account.heartbeatHistory.fetch(ethHistory);
+      expect(historyAccount.records[0].signer.toBuffer()).to.deep.equal(
+        Buffer.concat([Buffer.alloc(12), Buffer.from(ethAddress)])
+      );
+
+      try {
+        await relay(0);
+        expect.fail("Spent nonce must be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidEthHeartbeatMessage");
+      }
+
+      await relay(1);
+      switchAccount = await program.account.switch.fetch(ethSwitch);
+      expect(switchAccount.ethNonce.toNumber()).to.equal(2);
+    });
+  });
 });
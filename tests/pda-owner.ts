@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Deadmansswitch } from "../target/types/deadmansswitch";
+import { PdaOwner } from "../target/types/pda_owner";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("pda-owner", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.deadmansswitch as Program<Deadmansswitch>;
+  const pdaOwner = anchor.workspace.pdaOwner as Program<PdaOwner>;
+
+  // Separate rent payer - the PDA owner itself holds no lamports
+  const payer = provider.wallet as anchor.Wallet;
+  const beneficiary = Keypair.generate();
+  const switchId = "treasury";
+
+  let authority: PublicKey;
+  let switchPda: PublicKey;
+  let escrowPda: PublicKey;
+  let beneficiaryIndex: PublicKey;
+  let historyPda: PublicKey;
+
+  before(() => {
+    [authority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("authority")],
+      pdaOwner.programId
+    );
+
+    [switchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("switch"), authority.toBuffer(), Buffer.from(switchId)],
+      program.programId
+    );
+
+    [escrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), authority.toBuffer(), Buffer.from(switchId)],
+      program.programId
+    );
+
+    [historyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("history"), switchPda.toBuffer()],
+      program.programId
+    );
+
+    [beneficiaryIndex] = PublicKey.findProgramAddressSync(
+      [Buffer.from("beneficiary_index"), beneficiary.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Creates a switch owned by a PDA through CPI", async () => {
+    await pdaOwner.methods
+      .createSwitch(switchId, new BN(3600), beneficiary.publicKey)
+      .accounts({
+        authority,
+        switch: switchPda,
+        escrow: escrowPda,
+        beneficiaryIndex,
+        payer: payer.publicKey,
+        deadmansswitchProgram: program.programId,
+      })
+      .rpc();
+
+    const switchAccount = await program.account.switch.fetch(switchPda);
+    expect(switchAccount.owner.toString()).to.equal(authority.toString());
+    expect(switchAccount.status).to.have.property("active");
+
+    const index = await program.account.beneficiaryIndex.fetch(beneficiaryIndex);
+    expect(index.switches.map(k => k.toString())).to.include(switchPda.toString());
+  });
+
+  it("Funds the escrow from the payer", async () => {
+    await pdaOwner.methods
+      .fundSwitch(switchId, new BN(0.1 * LAMPORTS_PER_SOL))
+      .accounts({
+        authority,
+        escrow: escrowPda,
+        payer: payer.publicKey,
+      })
+      .rpc();
+
+    const balance = await provider.connection.getBalance(escrowPda);
+    expect(balance).to.equal(0.1 * LAMPORTS_PER_SOL);
+  });
+
+  it("Sends a heartbeat through CPI", async () => {
+    const switchBefore = await program.account.switch.fetch(switchPda);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    await pdaOwner.methods
+      .heartbeat(switchId)
+      .accounts({
+        authority,
+        switch: switchPda,
+        history: historyPda,
+        deadmansswitchProgram: program.programId,
+      })
+      .rpc();
+
+    const switchAfter = await program.account.switch.fetch(switchPda);
+    expect(switchAfter.heartbeatDeadline.toNumber()).to.be.greaterThan(
+      switchBefore.heartbeatDeadline.toNumber()
+    );
+  });
+
+  it("Reads the switch status through CPI", async () => {
+    await pdaOwner.methods
+      .assertAlive()
+      .accounts({
+        switch: switchPda,
+        escrow: escrowPda,
+        deadmansswitchProgram: program.programId,
+      })
+      .rpc();
+  });
+
+  it("Cancels the switch through CPI", async () => {
+    await pdaOwner.methods
+      .cancel(switchId)
+      .accounts({
+        authority,
+        switch: switchPda,
+        beneficiaryIndex,
+        deadmansswitchProgram: program.programId,
+      })
+      .rpc();
+
+    const switchAccount = await program.account.switch.fetch(switchPda);
+    expect(switchAccount.status).to.have.property("canceled");
+
+    const index = await program.account.beneficiaryIndex.fetch(beneficiaryIndex);
+    expect(index.switches).to.be.empty;
+  });
+});